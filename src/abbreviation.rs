@@ -0,0 +1,132 @@
+use std::{error::Error, fmt};
+
+use crate::{node::{NodeRef, NodeRefOps}, tree::Trie};
+
+/// Error returned when an abbreviation does not designate a single word
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ambiguity {
+    /// The abbreviation as typed
+    pub input:String,
+    /// Every word starting with the abbreviation, sorted alphabetically. Empty if no word does
+    pub candidates:Vec<String>,
+}
+
+impl Ambiguity {
+    /// Check if no word starts with the abbreviation at all
+    pub fn is_unknown(&self) -> bool {
+        self.candidates.is_empty()
+    }
+}
+
+impl fmt::Display for Ambiguity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_unknown() { return write!(f, "unknown: {:?}", self.input); }
+
+        write!(f, "{:?} is ambiguous, it could be {}", self.input, self.candidates.join(", "))
+    }
+}
+
+impl Error for Ambiguity {}
+
+pub trait Abbreviatable {
+    /// Get the word designated by `input`, either the word itself or the only word starting with it. A word typed in
+    /// full wins over the longer words it is a prefix of.
+    /// # Examples
+    /// ```
+    /// # use prefix::{abbreviation::Abbreviatable, tree::Trie};
+    /// let trie:Trie = Trie::from(["status", "stash", "commit", "co"]);
+    ///
+    /// assert_eq!(trie.resolve_abbreviation("stat"), Ok("status".to_string()));
+    /// assert_eq!(trie.resolve_abbreviation("co"), Ok("co".to_string()));
+    /// assert_eq!(trie.resolve_abbreviation("sta").unwrap_err().candidates, vec!["stash", "status"]);
+    /// assert!(trie.resolve_abbreviation("push").unwrap_err().is_unknown());
+    /// ```
+    /// # Time Complexity
+    /// Takes <i>O</i>(input + word) time when it resolves, <i>O</i>(n) time to list the candidates otherwise
+    fn resolve_abbreviation(&self, input:&str) -> Result<String, Ambiguity>;
+
+    /// Get the shortest abbreviation resolving to `word`, `None` if `word` isn't in the trie
+    /// # Examples
+    /// ```
+    /// # use prefix::{abbreviation::Abbreviatable, tree::Trie};
+    /// let trie:Trie = Trie::from(["status", "stash", "commit", "co"]);
+    ///
+    /// assert_eq!(trie.shortest_unique_prefix("status"), Some("stat".to_string()));
+    /// assert_eq!(trie.shortest_unique_prefix("commit"), Some("com".to_string()));
+    /// assert_eq!(trie.shortest_unique_prefix("co"), Some("co".to_string()));
+    /// assert_eq!(trie.shortest_unique_prefix("push"), None);
+    /// ```
+    /// # Time Complexity
+    /// Takes <i>O</i>(word) time
+    fn shortest_unique_prefix(&self, word:&str) -> Option<String>;
+
+    /// Get every word with its shortest abbreviation as `(word, abbreviation)`, sorted alphabetically by word
+    /// # Examples
+    /// ```
+    /// # use prefix::{abbreviation::Abbreviatable, tree::Trie};
+    /// let trie:Trie = Trie::from(["status", "stash"]);
+    ///
+    /// let help:Vec<String> = trie.unique_prefixes().iter()
+    ///     .map(|(word, abbr)| format!("{abbr}[{}]", &word[abbr.len()..]))
+    ///     .collect();
+    /// assert_eq!(help, vec!["stas[h]", "stat[us]"]);
+    /// ```
+    /// # Time Complexity
+    /// Takes <i>O</i>(n) time
+    fn unique_prefixes(&self) -> Vec<(String, String)>;
+}
+
+impl Abbreviatable for Trie {
+    fn resolve_abbreviation(&self, input:&str) -> Result<String, Ambiguity> {
+        let Some(cur) = self.go_to(input) else {
+            return Err(Ambiguity { input: input.to_string(), candidates: Vec::new() });
+        };
+
+        let node = cur.as_ref().borrow();
+        if node.is_end_of_word { return Ok(input.to_string()); }
+
+        let mut candidates:Vec<String> = Vec::with_capacity(node.words);
+        cur.collect_sorted(&mut input.to_string(), &mut candidates);
+
+        if candidates.len() == 1 { return Ok(candidates.remove(0)); }
+        Err(Ambiguity { input: input.to_string(), candidates })
+    }
+
+    fn shortest_unique_prefix(&self, word:&str) -> Option<String> {
+        let mut cur:NodeRef = self.root.clone();
+        // Length in bytes of the first prefix only `word` starts with
+        let mut unique:Option<usize> = None;
+
+        for (i, ch) in word.char_indices() {
+            let ochild:Option<NodeRef> = cur.as_ref().borrow().get_child(ch).cloned();
+            cur = ochild?;
+
+            if unique.is_none() && cur.as_ref().borrow().words == 1 { unique = Some(i + ch.len_utf8()); }
+        }
+        if !cur.as_ref().borrow().is_end_of_word { return None; }
+
+        Some(word[..unique.unwrap_or(word.len())].to_string())
+    }
+
+    fn unique_prefixes(&self) -> Vec<(String, String)> {
+        let mut res:Vec<(String, String)> = Vec::with_capacity(self.words);
+        collect_unique_prefixes(&self.root, &mut String::with_capacity(8), None, &mut res);
+
+        res
+    }
+}
+
+// Depth first over the sorted children, `unique` being the length of the first non empty prefix on the path with a
+// single word
+fn collect_unique_prefixes(node:&NodeRef, word:&mut String, unique:Option<usize>, res:&mut Vec<(String, String)>) {
+    let node = node.as_ref().borrow();
+    let unique:Option<usize> = unique.or((!word.is_empty() && node.words == 1).then_some(word.len()));
+
+    if node.is_end_of_word { res.push((word.clone(), word[..unique.unwrap_or(word.len())].to_string())); }
+
+    for (ch, child) in node.sorted_children() {
+        word.push(ch);
+        collect_unique_prefixes(&child, word, unique, res);
+        word.pop();
+    }
+}
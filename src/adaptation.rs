@@ -0,0 +1,121 @@
+use std::{collections::HashMap, time::{Duration, SystemTime, UNIX_EPOCH}};
+
+use crate::{autocomplete::collect_with_frequency, tree::Trie};
+
+/// What a user picked among completions, layered on top of a shared `Trie` to rank its completions for that user.
+///
+/// Every selection adds 1 to the score of the word, and scores halve every `half_life` so old habits fade. Completions
+/// are ranked by `ln(1 + frequency) + score`: a word picked recently gets ahead of words up to about `e` times more
+/// frequent. Layers can be serialized with the `serde` feature and merged, e.g. to carry a user's history over devices.
+///
+/// # Examples
+/// ```
+/// # use prefix::{adaptation::Adaptation, tree::Trie};
+/// let mut trie:Trie = Trie::new();
+/// trie.insert_with_frequency("the", 50);
+/// trie.insert_with_frequency("then", 10);
+/// trie.insert_with_frequency("theorem", 1);
+///
+/// let mut adaptation:Adaptation = Adaptation::default();
+/// assert_eq!(adaptation.complete(&trie, "the", 2), vec!["the", "then"]);
+///
+/// for _ in 0..3 { adaptation.record_selection("theorem"); }
+/// assert_eq!(adaptation.complete(&trie, "the", 2), vec!["the", "theorem"]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Adaptation {
+    half_life:Duration,
+    scores:HashMap<String, Score>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Score {
+    value:f64,
+    // Seconds since the Unix epoch at which `value` was reached
+    at:f64,
+}
+
+impl Default for Adaptation {
+    /// Scores halve every week
+    fn default() -> Self {
+        Self::new(Duration::from_secs(7 * 24 * 60 * 60))
+    }
+}
+
+impl Adaptation {
+    /// Create an empty `Adaptation` whose scores halve every `half_life`
+    pub fn new(half_life:Duration) -> Self {
+        Self { half_life, scores: HashMap::new() }
+    }
+
+    /// Record that `word` was picked now
+    pub fn record_selection(&mut self, word:&str) {
+        self.record_selection_at(word, SystemTime::now());
+    }
+
+    /// Record that `word` was picked at `at`, which may be older than selections already recorded
+    pub fn record_selection_at(&mut self, word:&str, at:SystemTime) {
+        self.add(word, Score { value: 1.0, at: seconds(at) });
+    }
+
+    /// Score of `word` at `now`, 0 if it was never picked
+    pub fn score_at(&self, word:&str, now:SystemTime) -> f64 {
+        self.scores.get(word).map_or(0.0, |score| self.decay(*score, seconds(now)))
+    }
+
+    /// Add the selections recorded by `other`, as if they had been recorded here. Scores keep this layer's half-life
+    pub fn merge(&mut self, other:&Adaptation) {
+        for (word, score) in other.scores.iter() { self.add(word, *score); }
+    }
+
+    /// Get the `k` words of `trie` beginning with `prefix`, the prefix included, best ranked first now, ties in
+    /// alphabetical order
+    /// # Time Complexity
+    /// Takes <i>O</i>(n log n) time, n being the number of words beginning with `prefix`
+    pub fn complete(&self, trie:&Trie, prefix:&str, k:usize) -> Vec<String> {
+        self.complete_at(trie, prefix, k, SystemTime::now())
+    }
+
+    /// Same as `complete`, ranked as they would be at `now`
+    pub fn complete_at(&self, trie:&Trie, prefix:&str, k:usize, now:SystemTime) -> Vec<String> {
+        let Some(cur) = trie.go_to(prefix) else { return Vec::new(); };
+
+        let mut words:Vec<(String, usize)> = Vec::with_capacity(cur.as_ref().borrow().words);
+        collect_with_frequency(&cur, &mut prefix.to_string(), 0, None, &mut words);
+
+        let mut ranked:Vec<(String, f64)> = words.into_iter()
+            .map(|(word, frequency)| {
+                let rank:f64 = (frequency as f64).ln_1p() + self.score_at(&word, now);
+                (word, rank)
+            })
+            .collect();
+        ranked.sort_unstable_by(|(a, ra), (b, rb)| rb.total_cmp(ra).then_with(|| a.cmp(b)));
+
+        ranked.into_iter().take(k).map(|(word, _)| word).collect()
+    }
+
+    // Sum two scores, brought to the later of their times
+    fn add(&mut self, word:&str, score:Score) {
+        let Some(cur) = self.scores.get(word).copied() else {
+            self.scores.insert(word.to_string(), score);
+            return;
+        };
+
+        let at:f64 = cur.at.max(score.at);
+        let value:f64 = self.decay(cur, at) + self.decay(score, at);
+        self.scores.insert(word.to_string(), Score { value, at });
+    }
+
+    fn decay(&self, score:Score, now:f64) -> f64 {
+        let half_life:f64 = self.half_life.as_secs_f64();
+        if half_life == 0.0 { return if now > score.at { 0.0 } else { score.value }; }
+
+        score.value * 0.5_f64.powf((now - score.at).max(0.0) / half_life)
+    }
+}
+
+fn seconds(time:SystemTime) -> f64 {
+    time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs_f64()
+}
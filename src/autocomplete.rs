@@ -1,17 +1,81 @@
-use crate::{node::NodeRefOps, tree::Trie};
+use std::collections::BTreeMap;
+
+use crate::{error_check::edit_row, node::{NodeRef, NodeRefOps, char_bit, fold_case}, tree::Trie};
+
+/// Order of the words returned by `AutoCompletable::complete_with`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CompletionSort {
+    /// Alphabetical order
+    #[default]
+    Alpha,
+    /// Most frequent first, ties in alphabetical order
+    Frequency,
+    /// Shortest first, ties in alphabetical order
+    Length,
+}
+
+/// What `AutoCompletable::complete_with` returns and in which order. The default gives the same words as `complete`,
+/// sorted alphabetically.
+///
+/// # Examples
+/// ```
+/// # use prefix::autocomplete::{CompletionOptions, CompletionSort};
+/// let options = CompletionOptions {
+///     full_words: true, sort: CompletionSort::Frequency, limit: Some(5), ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CompletionOptions {
+    /// Return whole words instead of the suffixes following the prefix
+    pub full_words:bool,
+    /// Also return the prefix itself when it is a word, as an empty suffix if `full_words` is off
+    pub include_exact:bool,
+    pub sort:CompletionSort,
+    /// Return at most this many words, the first ones in the chosen order
+    pub limit:Option<usize>,
+    /// Minimum length of the whole words, in chars
+    pub min_len:usize,
+    /// Maximum length of the whole words, in chars
+    pub max_len:Option<usize>,
+}
+
+impl CompletionOptions {
+    // Filter, sort, cut and shape candidates given as whole words that start with `prefix`, with their frequency
+    pub(crate) fn apply(&self, prefix:&str, mut candidates:Vec<(String, usize)>) -> Vec<String> {
+        candidates.retain(|(word, _)| {
+            let len:usize = word.chars().count();
+            let exact:bool = word.len() == prefix.len();
+            (self.include_exact || !exact) && len >= self.min_len && self.max_len.is_none_or(|max| len <= max)
+        });
+
+        match self.sort {
+            CompletionSort::Alpha => candidates.sort_unstable_by(|(a, _), (b, _)| a.cmp(b)),
+            CompletionSort::Frequency => {
+                candidates.sort_unstable_by(|(a, fa), (b, fb)| fb.cmp(fa).then_with(|| a.cmp(b)))
+            },
+            CompletionSort::Length => candidates.sort_unstable_by_key(|(word, _)| (word.chars().count(), word.clone())),
+        }
+        if let Some(limit) = self.limit { candidates.truncate(limit); }
+
+        candidates.into_iter()
+            .map(|(word, _)| if self.full_words { word } else { word[prefix.len()..].to_string() })
+            .collect()
+    }
+}
 
 pub trait AutoCompletable {
     /// Get only the words that begin with the provided suffix, excludes the provided suffix from the resulting words, 
     /// returns unsorted array of suffixes.
     /// # Examples
     /// ```
+    /// # use prefix::{autocomplete::AutoCompletable, tree::Trie};
     /// let mut trie:Trie = Trie::new();
     /// 
-    /// trie.inser("and");
-    /// trie.inser("ant");
-    /// trie.inser("anymore");
+    /// trie.insert("and");
+    /// trie.insert("ant");
+    /// trie.insert("anymore");
     /// 
-    /// let mut sufs:Vec<String> = t2.complete("an");
+    /// let mut sufs:Vec<String> = trie.complete("an");
     /// sufs.sort();
     /// 
     /// assert_eq!(vec!["d".to_string(), "t".to_string(), "ymore".to_string()], sufs);
@@ -19,6 +83,91 @@ pub trait AutoCompletable {
     /// # Time Complexity
     /// Takes <i>O</i>(n) time
     fn complete(&self, prefix:&str) -> Vec<String>;
+
+    /// Extend the prefix as far as every word starting with it agrees, as shell tab completion does. Stops at the
+    /// first node that ends a word or branches, returns the prefix unchanged if no word starts with it.
+    /// # Examples
+    /// ```
+    /// # use prefix::{autocomplete::AutoCompletable, tree::Trie};
+    /// let trie:Trie = Trie::from(["install", "instance", "instant", "in"]);
+    /// 
+    /// assert_eq!(trie.extend_unambiguous("ins"), "insta");
+    /// assert_eq!(trie.extend_unambiguous("instan"), "instan");
+    /// assert_eq!(trie.extend_unambiguous("i"), "in");
+    /// assert_eq!(trie.extend_unambiguous("in"), "in");
+    /// assert_eq!(trie.extend_unambiguous("out"), "out");
+    /// ```
+    /// # Time Complexity
+    /// Takes <i>O</i>(prefix + extension) time
+    fn extend_unambiguous(&self, prefix:&str) -> String;
+
+    /// Get the words that begin with the provided prefix grouped by the char following the prefix, each group sorted
+    /// alphabetically. The prefix itself is left out, having no next char.
+    /// # Examples
+    /// ```
+    /// # use prefix::{autocomplete::AutoCompletable, tree::Trie};
+    /// # use std::collections::BTreeMap;
+    /// let trie:Trie = Trie::from(["git", "git-add", "git-am", "git-blame"]);
+    /// 
+    /// let groups:BTreeMap<char, Vec<String>> = trie.complete_grouped("git-");
+    /// assert_eq!(groups[&'a'], vec!["git-add", "git-am"]);
+    /// assert_eq!(groups[&'b'], vec!["git-blame"]);
+    /// ```
+    /// # Time Complexity
+    /// Takes <i>O</i>(n) time
+    fn complete_grouped(&self, prefix:&str) -> BTreeMap<char, Vec<String>>;
+
+    /// Get the words that begin with the provided prefix, shaped, filtered, sorted and cut as told by `options`
+    /// # Examples
+    /// ```
+    /// # use prefix::{autocomplete::{AutoCompletable, CompletionOptions, CompletionSort}, tree::Trie};
+    /// let mut trie:Trie = Trie::from(["an", "and", "anymore"]);
+    /// trie.insert_with_frequency("ant", 5);
+    ///
+    /// let options = CompletionOptions { full_words: true, include_exact: true, ..Default::default() };
+    /// assert_eq!(trie.complete_with("an", &options), vec!["an", "and", "ant", "anymore"]);
+    ///
+    /// let options = CompletionOptions { sort: CompletionSort::Frequency, limit: Some(2), ..Default::default() };
+    /// assert_eq!(trie.complete_with("an", &options), vec!["t", "d"]);
+    ///
+    /// let options = CompletionOptions { full_words: true, max_len: Some(3), ..Default::default() };
+    /// assert_eq!(trie.complete_with("an", &options), vec!["and", "ant"]);
+    /// ```
+    /// # Time Complexity
+    /// Takes <i>O</i>(n log n) time
+    fn complete_with(&self, prefix:&str, options:&CompletionOptions) -> Vec<String>;
+
+    /// Get up to `k` whole words beginning with something at most `max_edits` edits away from the provided prefix,
+    /// an edit being an insertion, a deletion, a substitution or a swap of two adjacent chars. Closest first, then most
+    /// frequent, then in alphabetical order.
+    /// # Examples
+    /// ```
+    /// # use prefix::{autocomplete::AutoCompletable, tree::Trie};
+    /// let mut trie:Trie = Trie::from(["apple", "applet", "apply", "maple"]);
+    /// trie.insert_with_frequency("apply", 10);
+    ///
+    /// assert_eq!(trie.complete_fuzzy("aplp", 1, 3), vec!["apply", "apple", "applet"]);
+    /// assert_eq!(trie.complete_fuzzy("map", 1, 2), vec!["maple", "apply"]);
+    /// ```
+    /// # Time Complexity
+    /// Takes <i>O</i>(prefix * visited nodes) time, branches are left as soon as they are more than `max_edits` away
+    fn complete_fuzzy(&self, prefix:&str, max_edits:usize, k:usize) -> Vec<String>;
+
+    /// Get the words containing the chars of `query` in order, not necessarily next to each other, as fuzzy finders of
+    /// editors do. Case is ignored. Best matches first: chars matched at the start of a word or of a camelCase or
+    /// snake_case part, and chars matched one after the other, score higher; ties go to the shorter word, then in
+    /// alphabetical order.
+    /// # Examples
+    /// ```
+    /// # use prefix::{autocomplete::AutoCompletable, tree::Trie};
+    /// let trie:Trie = Trie::from(["getWords", "get_sorted_words", "go_to", "gateway"]);
+    ///
+    /// assert_eq!(trie.complete_subsequence("gW"), vec!["getWords", "get_sorted_words", "gateway"]);
+    /// assert_eq!(trie.complete_subsequence("gtwrd"), vec!["getWords", "get_sorted_words"]);
+    /// ```
+    /// # Time Complexity
+    /// Takes <i>O</i>(query * visited nodes) time, branches missing some of the chars left to match are skipped
+    fn complete_subsequence(&self, query:&str) -> Vec<String>;
 }
 
 impl AutoCompletable for Trie {
@@ -27,11 +176,214 @@ impl AutoCompletable for Trie {
 
         let Some(cur) = self.go_to(prefix) else { return res; };
         
-        for (_, node) in cur.as_ref().borrow().get_children() {
+        for node in cur.as_ref().borrow().get_children().values() {
             let mut suf:Vec<String> = node.clone().preorder();
             res.append(&mut suf);
         }
 
         res
     }
-}
\ No newline at end of file
+
+    fn extend_unambiguous(&self, prefix:&str) -> String {
+        let mut res:String = prefix.to_string();
+
+        let Some(mut cur) = self.go_to(prefix) else { return res; };
+        loop {
+            let next:Option<(char, NodeRef)> = {
+                let node = cur.as_ref().borrow();
+                if node.is_end_of_word || node.children_size() != 1 { None }
+                else { node.get_children().iter().next().map(|(ch, child)| (*ch, child.clone())) }
+            };
+
+            let Some((ch, child)) = next else { break; };
+            res.push(ch);
+            cur = child;
+        }
+
+        res
+    }
+
+    fn complete_grouped(&self, prefix:&str) -> BTreeMap<char, Vec<String>> {
+        let mut res:BTreeMap<char, Vec<String>> = BTreeMap::new();
+
+        let Some(cur) = self.go_to(prefix) else { return res; };
+
+        let mut word:String = prefix.to_string();
+        for (ch, node) in cur.as_ref().borrow().get_children().iter() {
+            let mut words:Vec<String> = Vec::with_capacity(node.as_ref().borrow().words);
+            word.push(*ch);
+            node.collect_sorted(&mut word, &mut words);
+            word.pop();
+
+            res.insert(*ch, words);
+        }
+
+        res
+    }
+
+    fn complete_with(&self, prefix:&str, options:&CompletionOptions) -> Vec<String> {
+        let Some(cur) = self.go_to(prefix) else { return Vec::new(); };
+
+        let mut candidates:Vec<(String, usize)> = Vec::with_capacity(cur.as_ref().borrow().words);
+        let depth:usize = prefix.chars().count();
+        collect_with_frequency(&cur, &mut prefix.to_string(), depth, options.max_len, &mut candidates);
+
+        options.apply(prefix, candidates)
+    }
+
+    fn complete_fuzzy(&self, prefix:&str, max_edits:usize, k:usize) -> Vec<String> {
+        rank_fuzzy(fuzzy_candidates(self, prefix, max_edits), k)
+    }
+
+    fn complete_subsequence(&self, query:&str) -> Vec<String> {
+        rank_subsequence(subsequence_candidates(self, query))
+    }
+}
+
+// Words of the trie close enough to the prefix, as `(word, distance, frequency)`
+pub(crate) fn fuzzy_candidates(trie:&Trie, prefix:&str, max_edits:usize) -> Vec<(String, usize, usize)> {
+    let query:Vec<char> = prefix.chars().collect();
+    let first:Vec<usize> = (0..=query.len()).collect();
+
+    let mut candidates:Vec<(String, usize, usize)> = Vec::with_capacity(16);
+    let fuzzy = Fuzzy { query: &query, max_edits };
+    fuzzy.collect(&trie.root, &mut String::with_capacity(8), &first, None, None, &mut candidates);
+
+    candidates
+}
+
+pub(crate) fn rank_fuzzy(mut candidates:Vec<(String, usize, usize)>, k:usize) -> Vec<String> {
+    candidates.sort_unstable_by(|(a, da, fa), (b, db, fb)| da.cmp(db).then(fb.cmp(fa)).then_with(|| a.cmp(b)));
+    candidates.truncate(k);
+
+    candidates.into_iter().map(|(word, _, _)| word).collect()
+}
+
+// Words of the trie the query is a subsequence of, as `(word, score)`
+pub(crate) fn subsequence_candidates(trie:&Trie, query:&str) -> Vec<(String, i32)> {
+    let query:Vec<char> = query.chars().collect();
+
+    // needs[j]: chars still to be found once the first j chars of the query are matched
+    let mut needs:Vec<u64> = vec![0; query.len() + 1];
+    for j in (0..query.len()).rev() { needs[j] = needs[j + 1] | char_bit(query[j]); }
+
+    let subsequence = Subsequence { query: &query, needs };
+    let mut states:Vec<Option<i32>> = vec![None; 2 * (query.len() + 1)];
+    states[0] = Some(0);
+
+    let mut candidates:Vec<(String, i32)> = Vec::with_capacity(16);
+    subsequence.collect(&trie.root, &mut String::with_capacity(8), None, &states, &mut candidates);
+
+    candidates
+}
+
+pub(crate) fn rank_subsequence(mut candidates:Vec<(String, i32)>) -> Vec<String> {
+    candidates.sort_unstable_by(|(a, sa), (b, sb)| {
+        sb.cmp(sa).then(a.chars().count().cmp(&b.chars().count())).then_with(|| a.cmp(b))
+    });
+    candidates.into_iter().map(|(word, _)| word).collect()
+}
+
+struct Fuzzy<'q> {
+    query:&'q [char],
+    max_edits:usize,
+}
+
+impl Fuzzy<'_> {
+    // Depth first, `row` being the edit distances between the prefixes of the query and the word read so far, `before`
+    // the row preceding it with the last char of the word, `best` the smallest distance to the whole query of a prefix
+    // of that word. Words are gathered as `(word, distance, frequency)`
+    fn collect(&self, node:&NodeRef, word:&mut String, row:&[usize], before:Option<(&[usize], char)>,
+        best:Option<usize>, res:&mut Vec<(String, usize, usize)>) {
+        let last:usize = row[row.len() - 1];
+        let best:Option<usize> = if last <= self.max_edits { Some(best.map_or(last, |b| b.min(last))) } else { best };
+
+        // No longer prefix can get within reach, every word below is as far as the best prefix seen
+        if row.iter().all(|cell| *cell > self.max_edits) {
+            let Some(distance) = best else { return; };
+
+            let mut words:Vec<(String, usize)> = Vec::with_capacity(node.as_ref().borrow().words);
+            collect_with_frequency(node, word, 0, None, &mut words);
+            res.extend(words.into_iter().map(|(word, frequency)| (word, distance, frequency)));
+            return;
+        }
+
+        let node = node.as_ref().borrow();
+        if node.is_end_of_word && let Some(distance) = best { res.push((word.clone(), distance, node.frequency)); }
+
+        for (next, child) in node.get_children().iter() {
+            let next_row:Vec<usize> = edit_row(self.query, row, before, *next);
+
+            word.push(*next);
+            self.collect(child, word, &next_row, Some((row, *next)), best, res);
+            word.pop();
+        }
+    }
+}
+
+// Gather the words below `node` with their frequency, not going deeper than `max_len` chars
+pub(crate) fn collect_with_frequency(node:&NodeRef, word:&mut String, depth:usize, max_len:Option<usize>,
+    res:&mut Vec<(String, usize)>) {
+    let node = node.as_ref().borrow();
+    if node.is_end_of_word { res.push((word.clone(), node.frequency)); }
+    if max_len.is_some_and(|max| depth >= max) { return; }
+
+    for (ch, child) in node.get_children().iter() {
+        word.push(*ch);
+        collect_with_frequency(child, word, depth + 1, max_len, res);
+        word.pop();
+    }
+}
+
+struct Subsequence<'q> {
+    query:&'q [char],
+    needs:Vec<u64>,
+}
+
+impl Subsequence<'_> {
+    // Depth first, `states[2 * j + consecutive]` being the best score with the first `j` chars of the query matched,
+    // `consecutive` telling whether the last char of the word was one of them
+    fn collect(&self, node:&NodeRef, word:&mut String, prev:Option<char>, states:&[Option<i32>],
+        res:&mut Vec<(String, i32)>) {
+        let m:usize = self.query.len();
+        let node = node.as_ref().borrow();
+
+        if node.is_end_of_word && let Some(score) = states[2 * m].max(states[2 * m + 1]) {
+            res.push((word.clone(), score));
+        }
+
+        // A state is alive if the chars it still needs can all be found below
+        let alive = |j:usize| states[2 * j].is_some() || states[2 * j + 1].is_some();
+        if !(0..=m).any(|j| alive(j) && self.needs[j] & !node.below == 0) { return; }
+
+        for (ch, child) in node.get_children().iter() {
+            let mut next:Vec<Option<i32>> = vec![None; states.len()];
+
+            for (i, score) in states.iter().enumerate() {
+                let Some(score) = score else { continue; };
+                let (j, consecutive) = (i / 2, i % 2 == 1);
+
+                // Chars skipped between two matched ones cost a point
+                let skipped:i32 = score - i32::from(j > 0 && j < m);
+                next[2 * j] = next[2 * j].max(Some(skipped));
+
+                if j < m && fold_case(*ch) == fold_case(self.query[j]) {
+                    let matched:i32 = score + bonus(*ch, prev, consecutive, self.query[j]);
+                    next[2 * (j + 1) + 1] = next[2 * (j + 1) + 1].max(Some(matched));
+                }
+            }
+
+            word.push(*ch);
+            self.collect(child, word, Some(*ch), &next, res);
+            word.pop();
+        }
+    }
+}
+
+// Score of `ch` matching the query char `wanted`, `prev` being the char before it in the word
+fn bonus(ch:char, prev:Option<char>, consecutive:bool, wanted:char) -> i32 {
+    let boundary:bool = prev.is_none_or(|prev| !prev.is_alphanumeric());
+    let hump:bool = ch.is_uppercase() && prev.is_some_and(|prev| prev.is_lowercase() || prev.is_numeric());
+
+    1 + 8 * i32::from(boundary) + 6 * i32::from(hump) + 4 * i32::from(consecutive) + i32::from(ch == wanted)
+}
@@ -0,0 +1,55 @@
+use crate::{matcher::{MatchKind, Matcher}, tree::Trie};
+
+/// Masks the words of a trie, e.g. profanities, wherever they appear in a text.
+///
+/// Words are found in a single pass by a `Matcher` set up for moderation: case is ignored, only whole words are
+/// masked, so `"class"` is left alone when `"ass"` is censored, and the longest word wins when several start at the
+/// same place.
+///
+/// # Examples
+/// ```
+/// # use prefix::{censor::Censor, tree::Trie};
+/// let censor:Censor = Censor::from(&Trie::from(["Darn", "heck"]));
+///
+/// assert_eq!(censor.censor("Darn it, what the HECK, darnit"), "**** it, what the ****, darnit");
+/// assert!(!censor.is_clean("oh heck"));
+/// ```
+pub struct Censor {
+    matcher:Matcher,
+    mask:char,
+}
+
+impl Censor {
+    /// Set the char each char of a censored word is replaced by, `*` by default
+    pub fn mask(mut self, mask:char) -> Self {
+        self.mask = mask;
+        self
+    }
+
+    /// Also mask the words when they are part of longer words. Off by default
+    pub fn inside_words(mut self, yes:bool) -> Self {
+        self.matcher = self.matcher.whole_words(!yes);
+        self
+    }
+
+    /// Replace every char of every censored word found in `text` by the mask
+    pub fn censor(&self, text:&str) -> String {
+        self.matcher.replace_all(text, |word| word.chars().map(|_| self.mask).collect())
+    }
+
+    /// Check that `text` contains no censored word
+    pub fn is_clean(&self, text:&str) -> bool {
+        self.matcher.find_iter(text).next().is_none()
+    }
+}
+
+impl From<&Trie> for Censor {
+    fn from(value: &Trie) -> Self {
+        let matcher:Matcher = value.to_matcher()
+            .kind(MatchKind::LeftmostLongest)
+            .case_insensitive(true)
+            .whole_words(true);
+
+        Self { matcher, mask: '*' }
+    }
+}
@@ -0,0 +1,109 @@
+use std::marker::PhantomData;
+
+use crate::{node::{NodeRef, NodeRefOps}, tree::Trie};
+
+/// Position in a trie that follows a prefix being typed, created by `Trie::cursor`.
+///
+/// Every typed char moves the cursor one node down and every deleted char one node back up, so a typing session never
+/// walks down from the root again. Chars typed once no word starts with the prefix anymore are still tracked, deleting
+/// them brings the cursor back to the last valid node.
+///
+/// # Examples
+/// ```
+/// # use prefix::tree::Trie;
+/// let trie:Trie = Trie::from(["car", "card", "care", "cat"]);
+/// let mut cursor = trie.cursor();
+///
+/// cursor.push('c');
+/// cursor.push('a');
+/// assert_eq!(cursor.children(), vec!['r', 't']);
+///
+/// cursor.push('r');
+/// assert!(cursor.is_word());
+/// assert_eq!(cursor.completions(), vec!["car", "card", "care"]);
+///
+/// assert!(!cursor.push('x'));
+/// assert!(cursor.completions().is_empty());
+///
+/// cursor.pop();
+/// assert_eq!(cursor.prefix(), "car");
+/// ```
+pub struct TrieCursor<'a> {
+    // Borrowing the trie keeps it from changing under the cursor
+    trie:PhantomData<&'a Trie>,
+    // Nodes from the root to the current one
+    path:Vec<NodeRef>,
+    prefix:String,
+    // Number of chars typed past the last node found
+    dead:usize,
+}
+
+impl<'a> TrieCursor<'a> {
+    pub(crate) fn new(trie:&'a Trie) -> Self {
+        Self { trie: PhantomData, path: vec![trie.root.clone()], prefix: String::with_capacity(8), dead: 0 }
+    }
+
+    /// Type a char, returns whether some word still starts with the prefix
+    /// # Time Complexity
+    /// Takes <i>O</i>(1) time
+    pub fn push(&mut self, ch:char) -> bool {
+        self.prefix.push(ch);
+        if self.dead > 0 {
+            self.dead += 1;
+            return false;
+        }
+
+        let ochild:Option<NodeRef> = self.node().as_ref().borrow().get_child(ch).cloned();
+        match ochild {
+            Some(child) => {
+                self.path.push(child);
+                true
+            },
+            None => {
+                self.dead = 1;
+                false
+            },
+        }
+    }
+
+    /// Delete the last typed char and return it, `None` if nothing is typed
+    /// # Time Complexity
+    /// Takes <i>O</i>(1) time
+    pub fn pop(&mut self) -> Option<char> {
+        let ch:char = self.prefix.pop()?;
+        if self.dead > 0 { self.dead -= 1; } else { self.path.pop(); }
+
+        Some(ch)
+    }
+
+    /// Everything typed so far
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Check if the typed prefix is a word of the trie
+    pub fn is_word(&self) -> bool {
+        self.dead == 0 && self.node().as_ref().borrow().is_end_of_word
+    }
+
+    /// Chars that can be typed next and still lead to a word, in alphabetical order
+    pub fn children(&self) -> Vec<char> {
+        if self.dead > 0 { return Vec::new(); }
+
+        self.node().as_ref().borrow().get_children().keys().copied().collect()
+    }
+
+    /// All the words starting with the typed prefix, the prefix included if it is a word, sorted alphabetically
+    pub fn completions(&self) -> Vec<String> {
+        if self.dead > 0 { return Vec::new(); }
+
+        let mut words:Vec<String> = Vec::with_capacity(self.node().as_ref().borrow().words);
+        self.node().collect_sorted(&mut self.prefix.clone(), &mut words);
+
+        words
+    }
+
+    fn node(&self) -> &NodeRef {
+        &self.path[self.path.len() - 1]
+    }
+}
@@ -1,5 +1,7 @@
 use crate::tree::Trie;
 
+type Edit = fn(&Trie, word:&str, distance:usize) -> Vec<String>;
+
 pub trait ErrorCheckable {
     /// If word is not found in trie, try to find closest words to the provided string
     fn spelling_check(&self, word:&str) -> Option<Vec<String>>;
@@ -10,20 +12,25 @@ impl ErrorCheckable for Trie {
     fn spelling_check(&self, word:&str) -> Option<Vec<String>> {
         if self.contains(word) { return None; }
 
-        let mut res:Vec<String> = Vec::with_capacity(4);
+        let res:Vec<String> = suggestions(self, word);
+        if res.is_empty() { return None; }
 
-        let edits:[fn(&Trie, word:&str, distance:usize) -> Vec<String>; 4] = [
-            Self::deletion, Self::transposition, Self::alteration, Self::insertion 
-        ];
+        Some(res)
+    }
+}
 
-        for edit in edits {
-            res.append( &mut edit(&self, word, 1) );
-        }
+// Words of the trie one edit away from `word`, which may itself come back when an edit leaves it unchanged
+pub(crate) fn suggestions(trie:&Trie, word:&str) -> Vec<String> {
+    let mut res:Vec<String> = Vec::with_capacity(4);
 
-        if res.is_empty() { return None; }
+    let edits:[Edit; 4] = [
+        Trie::deletion, Trie::transposition, Trie::alteration, Trie::insertion 
+    ];
 
-        Some(res)
+    for edit in edits {
+        res.append( &mut edit(trie, word, 1) );
     }
+    res
 }
 
 trait ErrorCheckEdits {
@@ -61,10 +68,12 @@ trait ErrorCheckEdits {
 impl ErrorCheckEdits for Trie {
     
     fn deletion(&self, word:&str, distance:usize) -> Vec<String> {
+        let chars:Vec<char> = word.chars().collect();
         let mut res:Vec<String> = Vec::with_capacity(4);
+        if chars.len() < distance { return res; }
 
-        for i in 0..word.len() - distance as usize + 1 {
-            let new_word:String = word[..i].to_string() + &word[i + distance as usize..];
+        for i in 0..chars.len() - distance + 1 {
+            let new_word:String = chars[..i].iter().chain(&chars[i + distance..]).collect();
             if self.contains(&new_word) { res.push(new_word); }
         }
         res
@@ -75,14 +84,17 @@ impl ErrorCheckEdits for Trie {
 
         let mut res:Vec<String> = Vec::with_capacity(4);
 
-        'outer: for d in 2..distance + 1 {
-            for i in 0..word.len() - d + 1 {
-                let mut chars_slice:Vec<char> = word[i..i + d].chars().collect();
+        let chars:Vec<char> = word.chars().collect();
+
+        'outer: for d in 2..(distance + 1).min(chars.len() + 1) {
+            for i in 0..chars.len() - d + 1 {
+                let mut chars_slice:Vec<char> = chars[i..i + d].to_vec();
             
                 let permutations:Vec<String> = permutations(&mut chars_slice);
+                let (left, right):(String, String) = (chars[..i].iter().collect(), chars[i + d..].iter().collect());
     
                 for perm in permutations {
-                    let new_word:String = word[0..i].to_string() + &perm + &word[i + d..];
+                    let new_word:String = left.clone() + &perm + &right;
                     if self.contains(&new_word) { 
                         res.push(new_word); 
                         break 'outer;
@@ -95,17 +107,21 @@ impl ErrorCheckEdits for Trie {
 
     //todo
     fn alteration(&self, word:&str, _distance:usize) -> Vec<String> {
-        let mut res:Vec<String> = Vec::with_capacity(word.len());
+        let chars:Vec<char> = word.chars().collect();
+        let mut res:Vec<String> = Vec::with_capacity(chars.len());
         
-        for (i, _) in word.char_indices() {
-            let (left, right) = ( &word[0..i], &word[i + 1..] );
+        for i in 0..chars.len() {
+            let (left, right):(String, String) = (chars[..i].iter().collect(), chars[i + 1..].iter().collect());
             
-            for n in 97..123 as u8 {
-                let new_word:String = left.to_string() + &n.to_string() + right;
+            for n in 97..123_u8 {
+                if n as char == chars[i] { continue; }
+                let mut new_word:String = String::with_capacity(left.len() + right.len() + 1);
+                new_word.push_str(&left);
+                new_word.push(n as char);
+                new_word.push_str(&right);
 
                 if self.contains(&new_word) { res.push(new_word);}
             }
-            println!()
         }
         res
     }
@@ -114,10 +130,11 @@ impl ErrorCheckEdits for Trie {
     fn insertion(&self, word:&str, _distance:usize) -> Vec<String> {
         let mut res:Vec<String> = Vec::with_capacity(16);
 
-        for (i, _) in word.char_indices() {
+        // Every char boundary, the end of the word included
+        for i in word.char_indices().map(|(i, _)| i).chain([word.len()]) {
             let (left, right) = ( &word[0..i], &word[i..] );
 
-            for n in 97..123 as u8 {
+            for n in 97..123_u8 {
                 let mut new_right:String = String::with_capacity(left.len() + right.len() + 1);
                 new_right.push(n as char);
                 new_right.push_str(right);
@@ -130,7 +147,7 @@ impl ErrorCheckEdits for Trie {
     }
 }
 
-fn permutations(chars:&mut Vec<char>) -> Vec<String> {
+fn permutations(chars:&mut [char]) -> Vec<String> {
     let n:usize = chars.len();
     let mut results:Vec<String> = Vec::new();
     let mut c:Vec<usize> = vec![0; n];
@@ -154,3 +171,23 @@ fn permutations(chars:&mut Vec<char>) -> Vec<String> {
     }
     results
 }
+
+// Next row of the optimal string alignment distance between `query` and a word read one char at a time, where `prev` is
+// the row for the word up to, but without, `ch` and `before` the row preceding it along with the char that led to
+// `prev`. The first row, for the empty word, is `0..=query.len()`; the last cell is the distance to the whole query.
+// Adjacent transpositions count as one edit, as in `transposition`.
+pub(crate) fn edit_row(query:&[char], prev:&[usize], before:Option<(&[usize], char)>, ch:char) -> Vec<usize> {
+    let mut row:Vec<usize> = Vec::with_capacity(prev.len());
+    row.push(prev[0] + 1);
+
+    for j in 1..prev.len() {
+        let substitution:usize = prev[j - 1] + usize::from(query[j - 1] != ch);
+        let mut cell:usize = (prev[j] + 1).min(row[j - 1] + 1).min(substitution);
+
+        if let Some((before, last)) = before && j > 1 && query[j - 1] == last && query[j - 2] == ch {
+            cell = cell.min(before[j - 2] + 1);
+        }
+        row.push(cell);
+    }
+    row
+}
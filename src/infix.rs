@@ -0,0 +1,39 @@
+use crate::tree::{Trie, inner_word_starts, unrotate};
+
+/// Completion matching any word of entries made of several words, e.g. `"york"` finds `"new york"`. Answered from the
+/// infix index when it is enabled with `Trie::enable_infix_index`, otherwise by going through every entry of the trie.
+pub trait InfixCompletable {
+    /// Get the whole entries having a word that begins with `query`, sorted alphabetically. The query may span several
+    /// words, `"york c"` finds `"new york city"`. Entries containing `'\u{1f}'` only match by their first word, with or
+    /// without the index.
+    /// # Examples
+    /// ```
+    /// # use prefix::{infix::InfixCompletable, tree::Trie};
+    /// let mut trie:Trie = Trie::from(["new york", "new york city", "yorkshire", "cork"]);
+    /// trie.enable_infix_index();
+    ///
+    /// assert_eq!(trie.complete_infix("york"), vec!["new york", "new york city", "yorkshire"]);
+    /// assert_eq!(trie.complete_infix("york c"), vec!["new york city"]);
+    /// ```
+    /// # Time Complexity
+    /// Takes <i>O</i>(query + result) time with the index, <i>O</i>(n) time without it
+    fn complete_infix(&self, query:&str) -> Vec<String>;
+}
+
+impl InfixCompletable for Trie {
+    fn complete_infix(&self, query:&str) -> Vec<String> {
+        let Some(infixes) = self.infixes.as_ref() else {
+            return self.get_sorted_words().into_iter()
+                .filter(|word| word.starts_with(query) || inner_word_starts(word).any(|i| word[i..].starts_with(query)))
+                .collect();
+        };
+
+        let mut res:Vec<String> = self.words_starting_with(query);
+        res.extend(infixes.words_starting_with(query).iter().map(|rotation| unrotate(rotation)));
+        res.sort();
+        // An entry may have several words beginning with the query
+        res.dedup();
+
+        res
+    }
+}
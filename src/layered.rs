@@ -0,0 +1,250 @@
+use std::collections::{BTreeMap, HashSet};
+
+use crate::{
+    autocomplete::{AutoCompletable, CompletionOptions, collect_with_frequency, fuzzy_candidates, rank_fuzzy,
+        rank_subsequence, subsequence_candidates},
+    error_check::{self, ErrorCheckable},
+    node::NodeRef,
+    tree::Trie,
+};
+
+/// Several tries queried as one, e.g. a language dictionary, a domain glossary and a user's own words.
+///
+/// Layers are ordered by priority, highest first, layers of equal priority in the order they were added. A word found
+/// in several layers is taken from the highest one, which shadows the others, e.g. for its frequency. A layer can also
+/// blacklist words, hiding them from the layers below it without touching their tries.
+///
+/// # Examples
+/// ```
+/// # use prefix::{autocomplete::AutoCompletable, layered::LayeredTrie, tree::Trie};
+/// let mut dictionary:LayeredTrie = LayeredTrie::new()
+///     .with_layer("english", 0, Trie::from(["pear", "peach", "pea"]))
+///     .with_layer("user", 10, Trie::from(["peachy"]));
+/// dictionary.blacklist("user", "pea");
+///
+/// assert!(dictionary.contains("peachy"));
+/// assert!(!dictionary.contains("pea"));
+///
+/// let mut words:Vec<String> = dictionary.complete("pe");
+/// words.sort();
+/// assert_eq!(words, vec!["ach", "achy", "ar"]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LayeredTrie {
+    // Highest priority first
+    layers:Vec<Layer>,
+}
+
+#[derive(Debug, Clone)]
+struct Layer {
+    name:String,
+    priority:i32,
+    trie:Trie,
+    blacklist:HashSet<String>,
+}
+
+impl LayeredTrie {
+    /// Create new `LayeredTrie` without any layer
+    pub fn new() -> Self {
+        Self { layers: Vec::new() }
+    }
+
+    /// Add a layer, replacing the one with the same name if any
+    pub fn with_layer(mut self, name:&str, priority:i32, trie:Trie) -> Self {
+        self.layers.retain(|layer| layer.name != name);
+
+        let i:usize = self.layers.iter().position(|layer| layer.priority < priority).unwrap_or(self.layers.len());
+        self.layers.insert(i, Layer { name: name.to_string(), priority, trie, blacklist: HashSet::new() });
+        self
+    }
+
+    /// Get the trie of a layer
+    pub fn layer(&self, name:&str) -> Option<&Trie> {
+        self.layers.iter().find(|layer| layer.name == name).map(|layer| &layer.trie)
+    }
+
+    /// Get the trie of a layer to change its words
+    pub fn layer_mut(&mut self, name:&str) -> Option<&mut Trie> {
+        self.layers.iter_mut().find(|layer| layer.name == name).map(|layer| &mut layer.trie)
+    }
+
+    /// Hide `word` from the layers below `name`, returns false if there is no such layer
+    pub fn blacklist(&mut self, name:&str, word:&str) -> bool {
+        let Some(layer) = self.layers.iter_mut().find(|layer| layer.name == name) else { return false; };
+
+        layer.blacklist.insert(word.to_string());
+        true
+    }
+
+    /// Show `word` again in the layers below `name`, returns false if it wasn't blacklisted there
+    pub fn unblacklist(&mut self, name:&str, word:&str) -> bool {
+        self.layers.iter_mut()
+            .find(|layer| layer.name == name)
+            .is_some_and(|layer| layer.blacklist.remove(word))
+    }
+
+    /// Check if `word` is in a layer and not blacklisted by a layer above it
+    /// # Time Complexity
+    /// Takes <i>O</i>(layers * word) time
+    pub fn contains(&self, word:&str) -> bool {
+        self.holder(word).is_some()
+    }
+
+    /// Frequency of `word` in the highest layer holding it, even if 0 there, 0 if it is in none or blacklisted above
+    pub fn frequency(&self, word:&str) -> usize {
+        self.holder(word).map_or(0, |layer| layer.trie.frequency(word))
+    }
+
+    // Highest layer holding `word`, unless a layer above it blacklists it
+    fn holder(&self, word:&str) -> Option<&Layer> {
+        for layer in self.layers.iter() {
+            if layer.trie.contains(word) { return Some(layer); }
+            if layer.blacklist.contains(word) { return None; }
+        }
+        None
+    }
+
+    // Blacklisted by a layer above the `i`th one
+    fn hidden(&self, i:usize, word:&str) -> bool {
+        self.layers[..i].iter().any(|layer| layer.blacklist.contains(word))
+    }
+
+    // Gather the candidates of every layer, keeping the first one found for each word, highest layer first, and
+    // leaving out those blacklisted above their layer
+    fn merge<T>(&self, candidates:impl Fn(&Trie) -> Vec<T>, word:impl Fn(&T) -> &str) -> Vec<T> {
+        let mut seen:HashSet<String> = HashSet::new();
+        let mut res:Vec<T> = Vec::new();
+
+        for (i, layer) in self.layers.iter().enumerate() {
+            for candidate in candidates(&layer.trie) {
+                let w:&str = word(&candidate);
+                if seen.contains(w) || self.hidden(i, w) { continue; }
+
+                seen.insert(w.to_string());
+                res.push(candidate);
+            }
+        }
+        res
+    }
+
+    // Words of the `i`th layer beginning with `prefix` and blacklisted above it
+    fn hidden_below(&self, i:usize, prefix:&str) -> HashSet<String> {
+        self.layers[..i].iter()
+            .flat_map(|layer| layer.blacklist.iter())
+            .filter(|word| word.starts_with(prefix) && self.layers[i].trie.contains(word))
+            .cloned()
+            .collect()
+    }
+
+    // Every visible word beginning with `prefix` with its frequency
+    fn words_with_frequency(&self, prefix:&str, max_len:Option<usize>) -> Vec<(String, usize)> {
+        let depth:usize = prefix.chars().count();
+
+        self.merge(|trie| {
+            let mut words:Vec<(String, usize)> = Vec::new();
+            if let Some(cur) = trie.go_to(prefix) {
+                collect_with_frequency(&cur, &mut prefix.to_string(), depth, max_len, &mut words);
+            }
+            words
+        }, |(word, _)| word)
+    }
+}
+
+// A layer's node reached by `LayeredTrie::extend_unambiguous`, with the words of the layer blacklisted above it
+struct Walk {
+    node:NodeRef,
+    hidden:HashSet<String>,
+}
+
+impl Walk {
+    // Some word below `node`, reached by `word`, isn't blacklisted
+    fn visible(&self, node:&NodeRef, word:&str) -> bool {
+        node.as_ref().borrow().words > self.hidden.iter().filter(|hidden| hidden.starts_with(word)).count()
+    }
+}
+
+impl AutoCompletable for LayeredTrie {
+    fn complete(&self, prefix:&str) -> Vec<String> {
+        self.words_with_frequency(prefix, None).into_iter()
+            .filter(|(word, _)| word.len() > prefix.len())
+            .map(|(word, _)| word[prefix.len()..].to_string())
+            .collect()
+    }
+
+    // Walks down every layer at once, a layer's node being followed only while some of the words below it aren't
+    // blacklisted above it. Takes O(layers * (prefix + extension)) time, times the number of blacklisted words
+    fn extend_unambiguous(&self, prefix:&str) -> String {
+        let mut res:String = prefix.to_string();
+        let mut walks:Vec<Walk> = self.layers.iter().enumerate()
+            .filter_map(|(i, layer)| Some(Walk { node: layer.trie.go_to(prefix)?, hidden: self.hidden_below(i, prefix) }))
+            .filter(|walk| walk.visible(&walk.node, &res))
+            .collect();
+        if walks.is_empty() { return res; }
+
+        loop {
+            let mut next:Option<char> = None;
+            for walk in walks.iter() {
+                let node = walk.node.as_ref().borrow();
+                if node.is_end_of_word && !walk.hidden.contains(&res) { return res; }
+
+                for (ch, child) in node.get_children().iter() {
+                    res.push(*ch);
+                    let visible:bool = walk.visible(child, &res);
+                    res.pop();
+
+                    if !visible { continue; }
+                    if next.is_some_and(|next| next != *ch) { return res; }
+                    next = Some(*ch);
+                }
+            }
+
+            // Each walk has a visible word below, its own or one of its children's
+            let Some(ch) = next else { return res; };
+            res.push(ch);
+            walks = walks.into_iter()
+                .filter_map(|walk| {
+                    let child:NodeRef = walk.node.as_ref().borrow().get_child(ch).cloned()?;
+                    walk.visible(&child, &res).then_some(Walk { node: child, hidden: walk.hidden })
+                })
+                .collect();
+        }
+    }
+
+    fn complete_grouped(&self, prefix:&str) -> BTreeMap<char, Vec<String>> {
+        let mut res:BTreeMap<char, Vec<String>> = BTreeMap::new();
+
+        for (word, _) in self.words_with_frequency(prefix, None) {
+            let Some(next) = word[prefix.len()..].chars().next() else { continue; };
+            res.entry(next).or_default().push(word);
+        }
+        for words in res.values_mut() { words.sort(); }
+
+        res
+    }
+
+    fn complete_with(&self, prefix:&str, options:&CompletionOptions) -> Vec<String> {
+        options.apply(prefix, self.words_with_frequency(prefix, options.max_len))
+    }
+
+    fn complete_fuzzy(&self, prefix:&str, max_edits:usize, k:usize) -> Vec<String> {
+        rank_fuzzy(self.merge(|trie| fuzzy_candidates(trie, prefix, max_edits), |(word, _, _)| word), k)
+    }
+
+    fn complete_subsequence(&self, query:&str) -> Vec<String> {
+        rank_subsequence(self.merge(|trie| subsequence_candidates(trie, query), |(word, _)| word))
+    }
+}
+
+impl ErrorCheckable for LayeredTrie {
+    fn spelling_check(&self, word:&str) -> Option<Vec<String>> {
+        if self.contains(word) { return None; }
+
+        // A layer may still hold `word` when it is blacklisted above it, so the layers' own check isn't enough
+        let res:Vec<String> = self.merge(|trie| {
+            error_check::suggestions(trie, word).into_iter().filter(|suggestion| suggestion != word).collect()
+        }, |word| word);
+        if res.is_empty() { return None; }
+
+        Some(res)
+    }
+}
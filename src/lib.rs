@@ -2,15 +2,34 @@ pub mod tree;
 mod node;
 pub mod error_check;
 pub mod autocomplete;
+pub mod render;
+pub mod rank;
+pub mod navigation;
+pub mod pattern;
+pub mod suffix;
+pub mod substring;
+pub mod matcher;
+pub mod censor;
+pub mod segment;
+pub mod cursor;
+pub mod abbreviation;
+pub mod infix;
+pub mod ngram;
+pub mod adaptation;
+pub mod layered;
+#[cfg(feature = "regex")]
+pub mod regex;
+#[cfg(feature = "serde")]
+pub mod serialize;
 
 #[macro_export]
 macro_rules! trie {
     [] => {
-        Trie::new()
+        $crate::tree::Trie::new()
     };
     [ $($word:expr),+ $(,)? ] => {
         {
-            let mut temp_trie = Trie::new();
+            let mut temp_trie = $crate::tree::Trie::new();
             $( temp_trie.insert($word); )*
             temp_trie
         }
@@ -22,9 +41,9 @@ macro_rules! trie {
 mod tests {
     const LINES:usize = 1000; // first n lines from file ./data/10k_cmn_words.txt
 
-    use std::{collections::HashMap, fs::File, io::{BufRead, BufReader, Read, Seek, SeekFrom}};
+    use std::{collections::{BTreeMap, HashMap, HashSet}, fs::File, hash::{Hash, Hasher}, io::{BufRead, BufReader, Read, Seek, SeekFrom}, time::{Duration, SystemTime}};
 
-    use crate::{autocomplete::AutoCompletable, tree::{Trie, TrieBuilder}};
+    use crate::{abbreviation::Abbreviatable, adaptation::Adaptation, autocomplete::{AutoCompletable, CompletionOptions, CompletionSort}, censor::Censor, error_check::{self, ErrorCheckable}, infix::InfixCompletable, layered::LayeredTrie, matcher::{MatchKind, Matcher}, navigation::Navigable, ngram::NgramTrie, pattern::PatternMatchable, rank::Rankable, render::Renderable, segment::{SegmentBy, Segmentable}, substring::SuffixTrie, suffix::SuffixSearchable, tree::{Trie, TrieBuilder}};
 
     #[test]
     fn insert() {
@@ -60,7 +79,43 @@ mod tests {
 
     #[test]
     fn delete() {
-        // i don't know what to do
+        let file = File::open("./data/10k_cmn_words.txt")
+        .expect("File not found");
+
+        let mut reader:BufReader<File> = BufReader::new(file);
+
+        let control_words:HashMap<char, Vec<String>> = get_first_n_words(&mut reader, LINES);
+
+        let mut trie:Trie = TrieBuilder::from(reader).lines(LINES).build();
+        let mut left:usize = trie.words;
+
+        for (i, word) in control_words.values().flatten().enumerate() {
+            if i % 2 == 1 { continue; }
+
+            trie.remove(word);
+            left -= 1;
+            assert!(!trie.contains(word), "Word [{word}] still in trie");
+            assert_eq!(left, trie.words);
+            assert_eq!(left, trie.count_prefix(""));
+        }
+
+        for (i, word) in control_words.values().flatten().enumerate() {
+            assert_eq!(trie.contains(word), i % 2 == 1, "Word [{word}]");
+        }
+
+        // Removing a missing word or a prefix that is not a word changes nothing
+        let mut trie:Trie = Trie::from(["and", "ant"]);
+        trie.remove("an");
+        trie.remove("bee");
+        trie.remove("");
+        assert_eq!(trie.words, 2);
+        assert_eq!(trie.count_prefix("an"), 2);
+
+        trie.remove("and");
+        assert_eq!(trie.child_counts("an"), vec![('t', 1)]);
+        trie.remove("ant");
+        assert_eq!(trie.root.as_ref().borrow().children_size(), 0);
+        assert_eq!(trie, Trie::new());
     }
 
     #[test]
@@ -76,7 +131,7 @@ mod tests {
 
         for (_, words) in control_words {
             for word in words {
-                assert_eq!(trie.contains(&word), true, "Word [{word}] not found in trie");
+                assert!(trie.contains(&word), "Word [{word}] not found in trie");
             } 
         }
     }
@@ -105,16 +160,787 @@ mod tests {
             control_suf.sort();
 
             assert_eq!( control_suf, trie_suf);
+
+            let options = CompletionOptions { include_exact: true, ..Default::default() };
+            assert_eq!(control_suf, trie.complete_with(prefix, &options));
+        }
+    }
+
+    #[test]
+    fn render() {
+        let trie:Trie = Trie::from(["to", "tea", "ten", "i"]);
+
+        let dot:String = trie.to_dot();
+        assert_eq!(dot.matches("shape=doublecircle").count(), trie.words);
+        assert_eq!(dot.matches(" -> ").count(), 6);
+
+        let sub:String = trie.subtree_to_dot("te").unwrap();
+        assert!(sub.contains("n0 [label=\"te\", shape=box];"));
+        assert_eq!(sub.matches(" -> ").count(), 2);
+        assert!(trie.subtree_to_dot("x").is_none());
+        let word:String = trie.to_dot_labeled("to", |word| Some(word.to_uppercase())).unwrap();
+        assert!(word.contains("n0 [label=\"to\", shape=box, peripheries=2, xlabel=\"TO\"];"));
+
+        let ascii:String = trie.to_ascii();
+        assert_eq!(ascii, [
+            ".",
+            "├── i *",
+            "└── t",
+            "    ├── e",
+            "    │   ├── a *",
+            "    │   └── n *",
+            "    └── o *",
+            "",
+        ].join("\n"));
+        assert_eq!(trie.subtree_to_ascii("te").unwrap(), "te\n├── a *\n└── n *\n");
+    }
+
+    #[test]
+    #[allow(clippy::mutable_key_type)]
+    fn equality() {
+        let trie1:Trie = crate::trie!["word", "here", "some"];
+        let trie2:Trie = Trie::from(["some", "word", "here"]);
+        assert_eq!(trie1, trie2);
+        assert_ne!(trie1, Trie::from(["some", "word"]));
+        assert!(trie1.eq_with_frequencies(&trie2));
+        let mut seen_twice:Trie = trie2.clone();
+        seen_twice.insert("word");
+        assert_eq!(seen_twice, trie1);
+        assert!(!seen_twice.eq_with_frequencies(&trie1));
+
+        let mut clone:Trie = trie1.clone();
+        assert_eq!(clone, trie1);
+        clone.insert("more");
+        assert!(!trie1.contains("more"));
+        assert_ne!(clone, trie1);
+
+        let hash = |trie:&Trie| {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            trie.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(&trie1), hash(&trie2));
+
+        let mut seen:HashSet<Trie> = HashSet::new();
+        seen.insert(trie1);
+        assert!(seen.contains(&trie2));
+    }
+
+    #[test]
+    fn format() {
+        let trie:Trie = Trie::from(["and", "a", "ant", "an"]);
+
+        assert_eq!(format!("{trie}"), "[a, an, and, ant]");
+        assert_eq!(format!("{trie:.2}"), "[a, an, … 2 more]");
+        assert_eq!(format!("{trie:.0}"), "[… 4 more]");
+        assert_eq!(format!("{}", Trie::new()), "[]");
+        assert_eq!(format!("{trie:#}"), trie.to_ascii());
+
+        assert_eq!(format!("{trie:?}"), r#"Trie { words: 4, entries: ["a", "an", "and", "ant"] }"#);
+        let mut many:Trie = Trie::new();
+        for i in 0..25 { many.insert(&format!("w{i:02}")); }
+        assert!(format!("{many:?}").ends_with(r#""w18", "w19", … 5 more] }"#));
+        let node:String = format!("{:?}", trie.go_to("an").unwrap().as_ref().borrow());
+        assert_eq!(node, "Node { val: 'n', is_end_of_word: true, words: 3, frequency: 1, children: ['d', 't'] }");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let (trie, _) = first_words();
+
+        let json:String = serde_json::to_string(&trie).unwrap();
+        let from_words:Trie = serde_json::from_str(&json).unwrap();
+        assert!(trie.eq_with_frequencies(&from_words));
+
+        let mut serializer = serde_json::Serializer::new(Vec::new());
+        crate::serialize::nested::serialize(&trie, &mut serializer).unwrap();
+        let nested:Vec<u8> = serializer.into_inner();
+        let mut deserializer = serde_json::Deserializer::from_slice(&nested);
+        let from_nested:Trie = crate::serialize::nested::deserialize(&mut deserializer).unwrap();
+        assert_eq!(trie.words, from_nested.words);
+        assert!(trie.eq_with_frequencies(&from_nested));
+
+        let small:Trie = Trie::from(["an", "a", "an"]);
+        let json:String = serde_json::to_string(&small).unwrap();
+        assert_eq!(json, r#"[["a",1],["an",2]]"#);
+        assert!(serde_json::from_str::<Trie>(&json).unwrap().eq_with_frequencies(&small));
+        let bare:Trie = serde_json::from_str(r#"["a","an",["an",1]]"#).unwrap();
+        assert!(bare.eq_with_frequencies(&small));
+    }
+
+    #[test]
+    fn rank_select() {
+        let (trie, control) = first_words();
+
+        for (i, word) in control.iter().enumerate() {
+            assert_eq!(trie.nth(i).as_ref(), Some(word));
+            assert_eq!(trie.rank(word), i);
+        }
+        assert_eq!(trie.nth(control.len()), None);
+
+        for probe in ["", "aa", "bz", "pri", "zzz"] {
+            let expected:usize = control.iter().filter(|word| word.as_str() < probe).count();
+            assert_eq!(trie.rank(probe), expected, "Rank of [{probe}]");
+        }
+
+        assert_eq!(trie.range(100..150), control[100..150]);
+        assert_eq!(trie.range(..=9), control[..10]);
+        assert_eq!(trie.range(control.len() - 3..), control[control.len() - 3..]);
+        assert!(trie.range(5..5).is_empty());
+        assert!(trie.range(control.len()..).is_empty());
+    }
+
+    #[test]
+    fn navigation() {
+        let (trie, control) = first_words();
+
+        assert_eq!(trie.first().as_ref(), control.first());
+        assert_eq!(trie.last().as_ref(), control.last());
+
+        for probe in ["", "a", "about", "abz", "pri", "the", "zzz"] {
+            let greater = control.iter().find(|word| word.as_str() > probe);
+            let smaller = control.iter().rev().find(|word| word.as_str() < probe);
+            let at_least = control.iter().find(|word| word.as_str() >= probe);
+            let at_most = control.iter().rev().find(|word| word.as_str() <= probe);
+
+            assert_eq!(trie.successor(probe).as_ref(), greater, "Successor of [{probe}]");
+            assert_eq!(trie.predecessor(probe).as_ref(), smaller, "Predecessor of [{probe}]");
+            assert_eq!(trie.ceiling(probe).as_ref(), at_least, "Ceiling of [{probe}]");
+            assert_eq!(trie.floor(probe).as_ref(), at_most, "Floor of [{probe}]");
+        }
+
+        assert_eq!(Trie::new().first(), None);
+        assert_eq!(Trie::new().last(), None);
+    }
+
+    #[test]
+    fn prefix_match() {
+        let trie:Trie = Trie::from(["un", "under", "understand", "über", "x"]);
+
+        assert_eq!(trie.prefixes_of("understandable"), vec!["un", "under", "understand"]);
+        assert_eq!(trie.longest_prefix_of("understandable"), Some("understand"));
+        assert_eq!(trie.longest_prefix_of("underway"), Some("under"));
+        assert_eq!(trie.longest_prefix_of("überall"), Some("über"));
+        assert_eq!(trie.longest_prefix_of("u"), None);
+        assert_eq!(trie.longest_prefix_of(""), None);
+        assert!(trie.prefixes_of("over").is_empty());
+    }
+
+    #[test]
+    fn wildcard() {
+        let (trie, control) = first_words();
+
+        let filter = |f:&dyn Fn(&str) -> bool| -> Vec<String> {
+            control.iter().filter(|word| f(word)).cloned().collect()
+        };
+
+        assert_eq!(trie.matches("*"), control);
+        assert_eq!(trie.matches("b*ing"), filter(&|w| w.len() >= 4 && w.starts_with('b') && w.ends_with("ing")));
+        assert_eq!(trie.matches("?a?"), filter(&|w| w.len() == 3 && &w[1..2] == "a"));
+        assert_eq!(trie.matches("*[aeiou][aeiou]*"), filter(&|w| {
+            w.as_bytes().windows(2).any(|p| p.iter().all(|c| b"aeiou".contains(c)))
+        }));
+        assert_eq!(trie.matches("[^a-s]*"), filter(&|w| !('a'..='s').contains(&w.chars().next().unwrap())));
+        assert_eq!(trie.matches("**e*e**"), filter(&|w| w.matches('e').count() >= 2));
+
+        let trie:Trie = Trie::from(["a[b", "a?", "ab"]);
+        assert_eq!(trie.matches("a[b"), vec!["a[b"]);
+        assert_eq!(trie.matches("a\\?"), vec!["a?"]);
+        assert_eq!(trie.matches("a[]?b]"), vec!["a?", "ab"]);
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn regex() {
+        use crate::regex::RegexSearchable;
+        use regex_automata::meta::Regex;
+
+        let (trie, control) = first_words();
+
+        for pattern in [r"^re[a-z]{2,4}ed$", "ing$", "^th", "ou", "^(a|b)+$", "^.{3}$", "x|q", "^$"] {
+            let regex:Regex = Regex::new(pattern).unwrap();
+            let expected:Vec<String> = control.iter().filter(|word| regex.is_match(word.as_str())).cloned().collect();
+
+            let words:Vec<String> = trie.regex_matches(pattern).unwrap().collect();
+            assert_eq!(words, expected, "Pattern [{pattern}]");
+        }
+
+        assert!(trie.regex_matches("(").is_err());
+        assert_eq!(trie.regex_matches("^zzz").unwrap().next(), None);
+    }
+
+    #[test]
+    fn suffix_index() {
+        let (mut trie, control) = first_words();
+
+        let without_index:Vec<String> = trie.ends_with("ing");
+        trie.enable_suffix_index();
+        assert_eq!(trie.ends_with("ing"), without_index);
+
+        for suffix in ["ing", "tion", "e", "", "xyz"] {
+            let expected:Vec<&String> = control.iter().filter(|word| word.ends_with(suffix)).collect();
+            assert_eq!(trie.ends_with(suffix).iter().collect::<Vec<_>>(), expected, "Suffix [{suffix}]");
+        }
+        for (prefix, suffix) in [("s", "s"), ("re", "ed"), ("", "ly"), ("th", ""), ("a", "a")] {
+            let expected:Vec<&String> = control.iter()
+                .filter(|word| word.starts_with(prefix) && word.ends_with(suffix))
+                .collect();
+            let words:Vec<String> = trie.starts_with_and_ends_with(prefix, suffix);
+            assert_eq!(words.iter().collect::<Vec<_>>(), expected, "Prefix [{prefix}] suffix [{suffix}]");
+        }
+
+        // The index follows insertions and removals
+        trie.insert("zzzing");
+        assert!(trie.ends_with("ing").contains(&"zzzing".to_string()));
+        trie.remove("zzzing");
+        trie.remove("thing");
+        assert!(!trie.ends_with("ing").iter().any(|word| word == "zzzing" || word == "thing"));
+        assert_eq!(trie.clone().ends_with("ing"), trie.ends_with("ing"));
+    }
+
+    #[test]
+    fn substring() {
+        let file = File::open("./data/10k_cmn_words.txt")
+        .expect("File not found");
+
+        let suffixes:SuffixTrie = TrieBuilder::from(BufReader::new(file)).lines(LINES).build_suffix_trie();
+
+        let (_, control) = first_words();
+        assert_eq!(suffixes.len(), control.len());
+
+        for sub in ["ough", "a", "tion", "the", "qq", "ing", ""] {
+            let expected:Vec<&str> = control.iter().filter(|word| word.contains(sub)).map(|w| w.as_str()).collect();
+            assert_eq!(suffixes.containing(sub), expected, "Substring [{sub}]");
+        }
+
+        let suffixes:SuffixTrie = SuffixTrie::from(&Trie::from(["banana", "bandana", "ana"]));
+        assert_eq!(suffixes.containing("ana"), vec!["ana", "banana", "bandana"]);
+        assert_eq!(suffixes.containing("nan"), vec!["banana"]);
+        assert_eq!(suffixes.containing("anab"), Vec::<&str>::new());
+        assert!(SuffixTrie::from(&Trie::new()).is_empty());
+    }
+
+    #[test]
+    fn multi_pattern() {
+        let words:[&str; 9] = ["he", "she", "his", "hers", "her", "a", "an", "and", "ü"];
+        let text:&str = "ushers and his hersheys, a band, üher";
+
+        // Brute force: every (start, end) where a word occurs
+        let mut all:Vec<(usize, usize)> = Vec::new();
+        for (start, _) in text.char_indices() {
+            for word in words.iter() {
+                if text[start..].starts_with(word) { all.push((start, start + word.len())); }
+            }
+        }
+
+        let mut overlapping:Vec<(usize, usize)> = Trie::from(words).to_matcher().kind(MatchKind::Overlapping)
+            .find_iter(text)
+            .map(|(start, end, word)| { assert_eq!(&text[start..end], word); (start, end) })
+            .collect();
+        overlapping.sort();
+        all.sort();
+        assert_eq!(overlapping, all);
+
+        let leftmost = |longest:bool| {
+            let mut res:Vec<(usize, usize)> = Vec::new();
+            let mut pos:usize = 0;
+            while let Some(start) = all.iter().map(|m| m.0).filter(|s| *s >= pos).min() {
+                let ends = all.iter().filter(|m| m.0 == start).map(|m| m.1);
+                // Leftmost-first picks the word that comes first in `words`, the insertion order
+                let first = words.iter().find(|word| text[start..].starts_with(**word)).map(|word| start + word.len());
+                let end:usize = if longest { ends.max().unwrap() } else { first.unwrap() };
+                res.push((start, end));
+                pos = end;
+            }
+            res
+        };
+
+        let matcher:Matcher = Trie::from(words).to_matcher();
+        let found:Vec<(usize, usize)> = matcher.find_iter(text).map(|(s, e, _)| (s, e)).collect();
+        assert_eq!(found, leftmost(true));
+
+        let matcher:Matcher = matcher.kind(MatchKind::LeftmostFirst);
+        let found:Vec<(usize, usize)> = matcher.find_iter(text).map(|(s, e, _)| (s, e)).collect();
+        assert_eq!(found, leftmost(false));
+
+        // A longer word starting earlier wins over a shorter one ending first
+        let matcher:Matcher = Trie::from(["bc", "abcd"]).to_matcher();
+        assert_eq!(matcher.find_iter("xabcd").collect::<Vec<_>>(), vec![(1, 5, "abcd")]);
+        assert_eq!(Trie::new().to_matcher().find_iter("text").next(), None);
+
+        // Leftmost-first follows the insertion order, not the length
+        let matcher:Matcher = Trie::from(["abcd", "ab"]).to_matcher().kind(MatchKind::LeftmostFirst);
+        assert_eq!(matcher.find_iter("abcd").collect::<Vec<_>>(), vec![(0, 4, "abcd")]);
+        let matcher:Matcher = Trie::from(["ab", "abcd"]).to_matcher().kind(MatchKind::LeftmostFirst);
+        assert_eq!(matcher.find_iter("abcd").collect::<Vec<_>>(), vec![(0, 2, "ab")]);
+    }
+
+    #[test]
+    fn replace() {
+        let matcher:Matcher = Trie::from(["new york", "york", "new"]).to_matcher();
+        let text:String = matcher.replace_all("New york, new york, newyork", |word| format!("<{word}>"));
+        assert_eq!(text, "New <york>, <new york>, <new><york>");
+
+        let matcher:Matcher = matcher.case_insensitive(true).whole_words(true);
+        let text:String = matcher.replace_all("New York, new york, newyork", |word| format!("<{word}>"));
+        assert_eq!(text, "<New York>, <new york>, newyork");
+
+        // Overlapping mode still replaces non-overlapping matches
+        let matcher:Matcher = matcher.kind(MatchKind::Overlapping);
+        assert_eq!(matcher.replace_all("new york", |_| "NY".to_string()), "NY");
+
+        let censor:Censor = Censor::from(&Trie::from(["ass", "bloody", "blood"])).mask('#');
+        assert_eq!(censor.censor("Bloody class, ASS! bloodyass"), "###### class, ###! bloodyass");
+        assert_eq!(censor.censor("Ünicode ass ü"), "Ünicode ### ü");
+        assert!(censor.is_clean("a classy passage"));
+
+        let censor:Censor = censor.inside_words(true);
+        assert_eq!(censor.censor("class bloodyass"), "cl### #########");
+
+        // Words in any case are censored in any case
+        let censor:Censor = Censor::from(&Trie::from(["Darn", "ÉTÉ"]));
+        assert_eq!(censor.censor("darn Darn DARN, été"), "**** **** ****, ***");
+
+        // Folding the words keeps their priority, "AB" still comes first
+        let matcher:Matcher = Trie::from(["AB", "abcd"]).to_matcher()
+            .kind(MatchKind::LeftmostFirst)
+            .case_insensitive(true);
+        assert_eq!(matcher.find_iter("ABCD").collect::<Vec<_>>(), vec![(0, 2, "AB")]);
+    }
+
+    #[test]
+    fn frequency() {
+        let mut trie:Trie = Trie::from(["to", "be", "or", "not", "to", "be"]);
+        trie.insert_with_frequency("be", 3);
+        assert_eq!(trie.words, 4);
+        assert_eq!(trie.frequency("be"), 5);
+        assert_eq!(trie.frequency("b"), 0);
+        assert_eq!(trie.total_frequency(), 9);
+
+        trie.remove("be");
+        assert_eq!(trie.total_frequency(), 4);
+        trie.insert("be");
+        assert_eq!(trie.frequency("be"), 1);
+        assert_eq!(trie.clone().frequency("to"), 2);
+    }
+
+    #[test]
+    fn segmentation() {
+        let file = File::open("./data/10k_cmn_words.txt")
+        .expect("File not found");
+
+        let trie:Trie = TrieBuilder::from(BufReader::new(file)).build();
+
+        assert_eq!(trie.segment("bestpizzaintown", SegmentBy::FewestWords), Some(vec!["best", "pizza", "in", "town"]));
+        assert_eq!(trie.segment("", SegmentBy::FewestWords), Some(vec![]));
+        assert_eq!(trie.segment("ßß", SegmentBy::Frequency), None);
+
+        let all:Vec<Vec<&str>> = trie.all_segmentations("bestpizzaintown", 1000);
+        assert!(all.len() > 1);
+        assert!(all.iter().all(|s| s.concat() == "bestpizzaintown" && s.iter().all(|w| trie.contains(w))));
+        assert!(all.contains(&vec!["best", "pizza", "in", "town"]));
+        assert_eq!(trie.all_segmentations("bestpizzaintown", 2).len(), 2);
+        assert!(trie.all_segmentations("bestpizzaintown", 0).is_empty());
+
+        let mut trie:Trie = Trie::new();
+        trie.insert_with_frequency("a", 100);
+        trie.insert_with_frequency("b", 100);
+        trie.insert_with_frequency("ab", 1);
+        assert_eq!(trie.segment("abab", SegmentBy::FewestWords), Some(vec!["ab", "ab"]));
+        assert_eq!(trie.segment("abab", SegmentBy::Frequency), Some(vec!["a", "b", "a", "b"]));
+    }
+
+    #[test]
+    fn cursor() {
+        let (trie, _) = first_words();
+        let mut cursor = trie.cursor();
+
+        for typed in ["pri", "prices", "q", "b"] {
+            while cursor.pop().is_some() {}
+            assert_eq!(cursor.prefix(), "");
+
+            for (i, ch) in typed.char_indices() {
+                let prefix:&str = &typed[..i + ch.len_utf8()];
+                assert_eq!(cursor.push(ch), trie.go_to(prefix).is_some());
+
+                let mut expected:Vec<String> = trie.complete(prefix).iter().map(|suf| format!("{prefix}{suf}")).collect();
+                if trie.contains(prefix) { expected.push(prefix.to_string()); }
+                expected.sort();
+
+                assert_eq!(cursor.prefix(), prefix);
+                assert_eq!(cursor.is_word(), trie.contains(prefix));
+                assert_eq!(cursor.completions(), expected);
+            }
+        }
+
+        // Backspacing out of a dead end goes back to the last valid node
+        while cursor.pop().is_some() {}
+        "bz".chars().chain("zz".chars()).for_each(|ch| { cursor.push(ch); });
+        assert!(cursor.children().is_empty());
+        cursor.pop();
+        cursor.pop();
+        cursor.pop();
+        assert_eq!(cursor.prefix(), "b");
+        assert_eq!(cursor.children(), trie.child_counts("b").iter().map(|(ch, _)| *ch).collect::<Vec<_>>());
+        assert_eq!(cursor.pop(), Some('b'));
+        assert_eq!(cursor.pop(), None);
+    }
+
+    #[test]
+    fn shell_completion() {
+        let (trie, _) = first_words();
+
+        for prefix in ["a", "pri", "abo", "thro", "q", "zz"] {
+            let extended:String = trie.extend_unambiguous(prefix);
+            assert!(extended.starts_with(prefix));
+
+            // Nothing is lost by extending
+            assert_eq!(trie.count_prefix(&extended), trie.count_prefix(prefix));
+            // And it cannot go further
+            if trie.count_prefix(prefix) > 0 && !trie.contains(&extended) {
+                assert!(trie.child_counts(&extended).len() > 1);
+            }
+
+            let groups:BTreeMap<char, Vec<String>> = trie.complete_grouped(prefix);
+            let mut grouped:Vec<String> = groups.values().flatten().cloned().collect();
+            if trie.contains(prefix) { grouped.push(prefix.to_string()); }
+            grouped.sort();
+
+            let mut expected:Vec<String> = trie.complete(prefix).iter().map(|suf| format!("{prefix}{suf}")).collect();
+            if trie.contains(prefix) { expected.push(prefix.to_string()); }
+            expected.sort();
+
+            assert_eq!(grouped, expected);
+            for (ch, words) in groups.iter() {
+                let next:String = format!("{prefix}{ch}");
+                assert!(words.iter().all(|word| word.starts_with(&next)));
+            }
         }
     }
 
+    #[test]
+    fn abbreviation() {
+        let (trie, _) = first_words();
+
+        let prefixes:Vec<(String, String)> = trie.unique_prefixes();
+        assert_eq!(prefixes.iter().map(|(word, _)| word.clone()).collect::<Vec<_>>(), trie.get_sorted_words());
+
+        for (word, abbr) in prefixes.iter() {
+            assert_eq!(trie.shortest_unique_prefix(word).as_ref(), Some(abbr));
+            assert_eq!(trie.resolve_abbreviation(abbr).as_ref(), Ok(word));
+
+            // One char less is ambiguous, unless that is another word typed in full
+            let shorter:&str = &abbr[..abbr.len() - abbr.chars().last().unwrap().len_utf8()];
+            if !shorter.is_empty() {
+                match trie.resolve_abbreviation(shorter) {
+                    Ok(other) => assert!(other == shorter && other != *word),
+                    Err(ambiguity) => assert!(ambiguity.candidates.len() > 1 && ambiguity.candidates.contains(word)),
+                }
+            }
+        }
+
+        let unknown = trie.resolve_abbreviation("qqq").unwrap_err();
+        assert!(unknown.is_unknown());
+        assert_eq!(trie.shortest_unique_prefix("qqq"), None);
+    }
+
+    #[test]
+    fn completion_options() {
+        let (mut trie, _) = first_words();
+        trie.insert_with_frequency("price", 40);
+        trie.insert_with_frequency("pride", 30);
+
+        let by_freq = CompletionOptions {
+            full_words: true, sort: CompletionSort::Frequency, limit: Some(2), ..Default::default()
+        };
+        assert_eq!(trie.complete_with("pri", &by_freq), vec!["price", "pride"]);
+
+        let by_len = CompletionOptions {
+            full_words: true, sort: CompletionSort::Length, min_len: 6, max_len: Some(7), ..Default::default()
+        };
+        let fits = |word:&String| word.starts_with("pri") && (6..=7).contains(&word.len());
+
+        let words:Vec<String> = trie.complete_with("pri", &by_len);
+        assert!(words.iter().all(fits));
+        assert!(words.windows(2).all(|pair| (pair[0].len(), &pair[0]) < (pair[1].len(), &pair[1])));
+        assert_eq!(words.len(), trie.get_sorted_words().iter().filter(|word| fits(word)).count());
+
+        assert!(trie.complete_with("qqq", &CompletionOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn fuzzy_completion() {
+        let (trie, _) = first_words();
+
+        // Smallest distance between the query and a prefix of the word, one prefix at a time
+        let distance = |query:&str, word:&str| -> usize {
+            let query:Vec<char> = query.chars().collect();
+            let mut rows:Vec<Vec<usize>> = vec![(0..=query.len()).collect()];
+            for (i, ch) in word.chars().enumerate() {
+                let before = (i > 0).then(|| (rows[i - 1].as_slice(), word.chars().nth(i - 1).unwrap()));
+                let row:Vec<usize> = error_check::edit_row(&query, &rows[i], before, ch);
+                rows.push(row);
+            }
+            rows.iter().map(|row| row[query.len()]).min().unwrap()
+        };
+
+        for (query, max_edits) in [("aplp", 1), ("teh", 1), ("prcie", 2), ("xq", 1)] {
+            let mut expected:Vec<(usize, String)> = trie.get_sorted_words().into_iter()
+                .map(|word| (distance(query, &word), word))
+                .filter(|(d, _)| *d <= max_edits)
+                .collect();
+            expected.sort();
+
+            let found:Vec<String> = trie.complete_fuzzy(query, max_edits, 10);
+            assert_eq!(found, expected.into_iter().take(10).map(|(_, word)| word).collect::<Vec<_>>());
+        }
+
+        assert!(trie.complete_fuzzy("apple", 0, 10).iter().all(|word| word.starts_with("apple")));
+    }
+
+    #[test]
+    fn subsequence_completion() {
+        let (mut trie, _) = first_words();
+
+        let is_subsequence = |query:&str, word:&str| {
+            let mut chars = word.chars();
+            query.chars().all(|q| chars.any(|ch| ch.eq_ignore_ascii_case(&q)))
+        };
+        let check = |trie:&Trie| {
+            for query in ["pcr", "THR", "aaa", "xyz", "e"] {
+                let mut found:Vec<String> = trie.complete_subsequence(query);
+                found.sort();
+
+                let expected:Vec<String> = trie.get_sorted_words().into_iter()
+                    .filter(|word| is_subsequence(query, word))
+                    .collect();
+                assert_eq!(found, expected);
+            }
+        };
+
+        check(&trie);
+        // Removed words take their chars away from the branches above them
+        for word in trie.get_sorted_words().into_iter().filter(|word| word.contains('y')) { trie.remove(&word); }
+        check(&trie);
+        assert!(trie.complete_subsequence("y").is_empty());
+
+        let symbols:Trie = Trie::from(["getWords", "get_sorted_words", "go_to", "gateway", "GetWindow"]);
+        assert_eq!(symbols.complete_subsequence("gw")[..2], ["getWords", "GetWindow"]);
+        assert_eq!(symbols.complete_subsequence("gt")[0], "go_to");
+    }
+
+    #[test]
+    fn infix_completion() {
+        let (mut plain, _) = first_words();
+        for place in ["new york", "new york city", "york", "los  angeles", "san jose", "san josé del cabo", "a\u{1f}b c"] {
+            plain.insert(place);
+        }
+        let mut indexed:Trie = plain.clone();
+        indexed.enable_infix_index();
+
+        for query in ["york", "new", "jos", "angeles", "del c", "c", "qq"] {
+            assert_eq!(plain.complete_infix(query), indexed.complete_infix(query));
+        }
+        assert_eq!(indexed.complete_infix("angel"), vec!["los  angeles"]);
+        assert_eq!(indexed.complete_infix("york"), vec!["new york", "new york city", "york"]);
+        // An entry holding the rotation mark is only found by its first word, never mangled
+        assert!(!indexed.complete_infix("c").iter().any(|word| word.contains('\u{1f}')));
+        assert_eq!(indexed.complete_infix("a\u{1f}"), vec!["a\u{1f}b c"]);
+
+        // The index follows insertions and removals
+        indexed.remove("new york city");
+        indexed.insert("old york road");
+        assert_eq!(indexed.complete_infix("york"), vec!["new york", "old york road", "york"]);
+        assert!(indexed.complete_infix("road").contains(&"old york road".to_string()));
+        assert!(!indexed.complete_infix("city").contains(&"new york city".to_string()));
+
+        indexed.disable_infix_index();
+        assert!(!indexed.has_infix_index());
+        assert_eq!(indexed.complete_infix("york"), vec!["new york", "old york road", "york"]);
+    }
+
+    #[test]
+    fn next_word_prediction() {
+        let file = File::open("./data/10k_cmn_words.txt")
+        .expect("File not found");
+
+        // One word per line, only single words are counted
+        let words:NgramTrie = TrieBuilder::from(BufReader::new(file)).lines(LINES).build_ngrams(3);
+        assert_eq!(words.count(&[]), LINES);
+        assert_eq!(words.count(&["of", "and"]), 0);
+        assert_eq!(words.predict_next(&["of"], 2).len(), 2);
+
+        let mut ngrams:NgramTrie = NgramTrie::new(3);
+        let corpus:[&str; 5] = ["i want to go home", "i want to eat", "we want to eat now", "they want pizza", "i like to eat"];
+        for sentence in corpus {
+            ngrams.add_sentence(&sentence.split_whitespace().collect::<Vec<&str>>());
+        }
+
+        assert_eq!(ngrams.count(&["want", "to"]), 3);
+        assert_eq!(ngrams.count(&["to", "eat", "now"]), 1);
+        // Sequences never go past the order
+        assert_eq!(ngrams.count(&["i", "want", "to", "go"]), 0);
+
+        assert_eq!(ngrams.predict_next(&["i", "want", "to"], 2), vec!["eat", "go"]);
+        assert_eq!(ngrams.predict_next(&["they", "want"], 1), vec!["pizza"]);
+        // "you want" was never seen, "want" alone was
+        assert_eq!(ngrams.predict_next(&["you", "want"], 2), vec!["to", "pizza"]);
+        // Unknown context, back to the most frequent words
+        assert_eq!(ngrams.predict_next(&["zebra"], 3), vec!["to", "want", "eat"]);
+        assert!(ngrams.predict_next(&["i"], 0).is_empty());
+    }
+
+    #[test]
+    fn adaptation() {
+        let (trie, _) = first_words();
+        let day:Duration = Duration::from_secs(24 * 60 * 60);
+        let start:SystemTime = SystemTime::UNIX_EPOCH + 1000 * day;
+
+        // Nothing picked yet, every word seen once: alphabetical order
+        let mut phone:Adaptation = Adaptation::new(day);
+        let mut expected:Vec<String> = trie.words_starting_with("pr");
+        expected.sort();
+        assert_eq!(phone.complete_at(&trie, "pr", 3, start), expected[..3]);
+
+        phone.record_selection_at("problem", start);
+        phone.record_selection_at("problem", start);
+        assert_eq!(phone.complete_at(&trie, "pr", 1, start), vec!["problem"]);
+        assert!((phone.score_at("problem", start + day) - 1.0).abs() < 1e-9);
+
+        // Picks made later on another device take over once merged
+        let mut laptop:Adaptation = Adaptation::new(day);
+        for _ in 0..3 { laptop.record_selection_at("process", start + 2 * day); }
+        phone.merge(&laptop);
+        assert_eq!(phone.complete_at(&trie, "pr", 2, start + 2 * day), vec!["process", "problem"]);
+        assert!((phone.score_at("problem", start + 2 * day) - 0.5).abs() < 1e-9);
+
+        // Long forgotten picks no longer matter
+        assert_eq!(phone.complete_at(&trie, "pr", 3, start + 100 * day), expected[..3]);
+        assert!(phone.complete_at(&trie, "qqq", 3, start).is_empty());
+
+        #[cfg(feature = "serde")]
+        {
+            let json:String = serde_json::to_string(&phone).unwrap();
+            let restored:Adaptation = serde_json::from_str(&json).unwrap();
+            assert_eq!(restored, phone);
+        }
+    }
+
+    #[test]
+    fn layered() {
+        let (base, _) = first_words();
+        let mut glossary:Trie = Trie::from(["prefix tree", "trie", "tries"]);
+        glossary.insert_with_frequency("problem", 7);
+
+        let mut dictionary:LayeredTrie = LayeredTrie::new()
+            .with_layer("user", 10, Trie::from(["pribble"]))
+            .with_layer("base", 0, base.clone())
+            .with_layer("glossary", 5, glossary);
+        assert!(dictionary.blacklist("glossary", "price"));
+        assert!(!dictionary.blacklist("nope", "price"));
+
+        assert!(dictionary.contains("pribble") && dictionary.contains("trie") && dictionary.contains("the"));
+        assert!(!dictionary.contains("price"));
+        // The glossary shadows the base dictionary
+        assert_eq!(dictionary.frequency("problem"), 7);
+
+        let mut expected:Vec<String> = base.words_starting_with("pri").into_iter()
+            .filter(|word| word != "price")
+            .collect();
+        expected.push("pribble".to_string());
+        expected.sort();
+
+        let options = CompletionOptions { full_words: true, include_exact: true, ..Default::default() };
+        assert_eq!(dictionary.complete_with("pri", &options), expected);
+
+        let mut suffixes:Vec<String> = dictionary.complete("pri");
+        suffixes.sort();
+        let expected_suffixes:Vec<String> = expected.iter()
+            .filter(|word| *word != "pri")
+            .map(|word| word[3..].to_string())
+            .collect();
+        assert_eq!(suffixes, expected_suffixes);
+
+        let by_freq = CompletionOptions {
+            full_words: true, sort: CompletionSort::Frequency, limit: Some(1), ..Default::default()
+        };
+        assert_eq!(dictionary.complete_with("pro", &by_freq), vec!["problem"]);
+
+        assert_eq!(dictionary.extend_unambiguous("prefix t"), "prefix tree");
+        assert_eq!(dictionary.extend_unambiguous("tri"), "trie");
+        // A word inserted with frequency 0 is still a word, and still shadows the layers below
+        let mut unseen:Trie = Trie::new();
+        unseen.insert_with_frequency("w", 0);
+        unseen.insert_with_frequency("zero", 0);
+        let shadowed:LayeredTrie = LayeredTrie::new()
+            .with_layer("hi", 1, unseen)
+            .with_layer("lo", 0, Trie::from(["w"; 9]));
+        assert!(shadowed.contains("zero"));
+        assert_eq!(shadowed.complete("z"), vec!["ero"]);
+        assert!(shadowed.contains("w"));
+        assert_eq!(shadowed.frequency("w"), 0);
+
+        let mut git:LayeredTrie = LayeredTrie::new()
+            .with_layer("builtin", 0, Trie::from(["stash", "status", "stage"]))
+            .with_layer("aliases", 1, Trie::from(["stat"]));
+        assert_eq!(git.extend_unambiguous("sta"), "sta");
+        git.blacklist("aliases", "stage");
+        assert_eq!(git.extend_unambiguous("sta"), "sta");
+        git.blacklist("aliases", "stash");
+        assert_eq!(git.extend_unambiguous("sta"), "stat");
+        git.blacklist("aliases", "stat");
+        assert_eq!(git.extend_unambiguous("sta"), "stat");
+        git.layer_mut("aliases").unwrap().remove("stat");
+        assert_eq!(git.extend_unambiguous("sta"), "status");
+        assert_eq!(dictionary.complete_grouped("trie")[&'s'], vec!["tries"]);
+        assert!(!dictionary.complete_fuzzy("pricr", 1, 50).contains(&"price".to_string()));
+        assert!(dictionary.complete_subsequence("pbl").contains(&"pribble".to_string()));
+
+        // A blacklisted word gets suggestions from the layers still holding it, without itself
+        let suggestions:Vec<String> = dictionary.spelling_check("price").unwrap();
+        assert!(suggestions.contains(&"prices".to_string()) && !suggestions.contains(&"price".to_string()));
+
+        // Unblacklisted words come back, the layers themselves were never changed
+        assert!(dictionary.unblacklist("glossary", "price"));
+        assert!(dictionary.contains("price"));
+        assert_eq!(dictionary.spelling_check("pribbles"), Some(vec!["pribble".to_string()]));
+        assert_eq!(dictionary.spelling_check("trie"), None);
+
+        dictionary.layer_mut("user").unwrap().insert("pricey");
+        assert_eq!(dictionary.layer("user").unwrap().words, 2);
+        assert!(dictionary.contains("pricey"));
+    }
+
     #[test]
     fn spelling_correction() {
-        
+        let trie:Trie = Trie::from(["cat", "cart", "act", "café", "cafés"]);
+
+        assert_eq!(trie.spelling_check("cat"), None);
+        assert_eq!(trie.spelling_check("cst"), Some(vec!["cat".to_string()]));
+        assert_eq!(trie.spelling_check("ca"), Some(vec!["cat".to_string()]));
+        assert_eq!(trie.spelling_check("cta"), Some(vec!["cat".to_string()]));
+        assert_eq!(trie.spelling_check("caf"), Some(vec!["cat".to_string()]));
+        assert_eq!(trie.spelling_check("cafés!"), Some(vec!["cafés".to_string()]));
+        assert_eq!(trie.spelling_check("é"), None);
+        assert_eq!(trie.spelling_check(""), None);
     }
 
     // Helper functions
 
+    // The trie of the first `LINES` words of the word list, with those words sorted alphabetically
+    fn first_words() -> (Trie, Vec<String>) {
+        let file = File::open("./data/10k_cmn_words.txt")
+        .expect("File not found");
+
+        let trie:Trie = TrieBuilder::from(BufReader::new(file)).lines(LINES).build();
+
+        let mut control:Vec<String> = trie.get_words().into_values().flatten().collect();
+        control.sort();
+
+        (trie, control)
+    }
+
     fn get_first_n_words(reader:&mut BufReader<File>, n:usize) -> HashMap<char, Vec<String>> {
         let mut words:HashMap<char, Vec<String>> = HashMap::with_capacity(26);
 
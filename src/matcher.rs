@@ -0,0 +1,318 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use crate::{node::NodeRef, tree::Trie};
+
+/// How `Matcher::find_iter` picks among matches that overlap
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MatchKind {
+    /// Non-overlapping matches, the one starting first wins, among those the longest one
+    #[default]
+    LeftmostLongest,
+    /// Non-overlapping matches, the one starting first wins, among those the word inserted first in the trie. Clones
+    /// keep the insertion order, serialized tries come back in alphabetical order
+    LeftmostFirst,
+    /// Every match, in the order they end, longest first when several end at the same position
+    Overlapping,
+}
+
+/// Aho-Corasick automaton finding all the words of a trie in a text in a single pass.
+///
+/// Every node of the trie becomes a state, to which are added a failure link, pointing to the state of the longest
+/// proper suffix of the node's word that is also in the trie, and an output link, pointing to the closest state along
+/// the failure links that ends a word.
+///
+/// # Examples
+/// ```
+/// # use prefix::{matcher::{MatchKind, Matcher}, tree::Trie};
+/// let trie:Trie = Trie::from(["he", "she", "hers", "his"]);
+/// let matcher:Matcher = trie.to_matcher();
+///
+/// let found:Vec<(usize, usize, &str)> = matcher.find_iter("ushers").collect();
+/// assert_eq!(found, vec![(1, 4, "she")]);
+///
+/// let matcher:Matcher = matcher.kind(MatchKind::Overlapping);
+/// let found:Vec<(usize, usize, &str)> = matcher.find_iter("ushers").collect();
+/// assert_eq!(found, vec![(1, 4, "she"), (2, 4, "he"), (2, 6, "hers")]);
+/// ```
+pub struct Matcher {
+    states:Vec<State>,
+    kind:MatchKind,
+    case_insensitive:bool,
+    whole_words:bool,
+    // Length in chars of the longest word
+    max_len:usize,
+}
+
+struct State {
+    next:BTreeMap<char, usize>,
+    fail:usize,
+    // Length in chars of the word ending at this state
+    word_len:Option<usize>,
+    // Insertion rank of the word ending at this state, the lower the higher its priority
+    order:usize,
+    // Closest state down the failure links that ends a word
+    output:Option<usize>,
+}
+
+// Id of the state built from the root of the trie
+const ROOT:usize = 0;
+
+impl Matcher {
+    /// Set how overlapping matches are reported, `MatchKind::LeftmostLongest` by default
+    pub fn kind(mut self, kind:MatchKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Ignore case: the text is lowercased while being read and the words are lowercased the same way, once, when it is
+    /// turned on. Turning it back off leaves the words lowercased. Off by default
+    /// # Examples
+    /// ```
+    /// # use prefix::{matcher::Matcher, tree::Trie};
+    /// let matcher:Matcher = Trie::from(["Darn", "heck"]).to_matcher().case_insensitive(true);
+    ///
+    /// assert_eq!(matcher.find_iter("darn HECK").count(), 2);
+    /// ```
+    pub fn case_insensitive(mut self, yes:bool) -> Self {
+        self.case_insensitive = yes;
+        if !yes || self.states.iter().all(|state| state.next.keys().all(|ch| fold(*ch) == *ch)) { return self; }
+
+        // Rebuilt from the lowercased words, inserted in their original order so that their priority is kept
+        let mut words:Vec<(usize, String)> = Vec::new();
+        self.folded_words(ROOT, &mut String::new(), &mut words);
+        words.sort();
+
+        let mut trie:Trie = Trie::new();
+        for (_, word) in words.iter() { trie.insert(word); }
+
+        let folded:Matcher = Matcher::from(&trie);
+        self.states = folded.states;
+        self.max_len = folded.max_len;
+        self
+    }
+
+    /// Only report words standing on their own, neither preceded nor followed by a letter, a digit or `_`. Off by
+    /// default
+    pub fn whole_words(mut self, yes:bool) -> Self {
+        self.whole_words = yes;
+        self
+    }
+
+    /// Iterate over the words found in `text` as `(start, end, word)`, where `start..end` is the byte range of `word`
+    /// in `text`
+    /// # Time Complexity
+    /// Takes <i>O</i>(text + matches) time in overlapping mode, leftmost modes may read again up to the length of the
+    /// longest word after each match
+    pub fn find_iter<'m, 't>(&'m self, text:&'t str) -> FindIter<'m, 't> {
+        self.find_iter_kind(text, self.kind)
+    }
+
+    /// Replace every word found in `text` by what `replacement` returns for it. Matches never overlap here, in
+    /// overlapping mode they are picked as with `MatchKind::LeftmostLongest`
+    /// # Examples
+    /// ```
+    /// # use prefix::{matcher::Matcher, tree::Trie};
+    /// let matcher:Matcher = Trie::from(["cat", "dog"]).to_matcher().whole_words(true);
+    ///
+    /// let text:String = matcher.replace_all("cat and dog, not catalog", |word| word.to_uppercase());
+    /// assert_eq!(text, "CAT and DOG, not catalog");
+    /// ```
+    pub fn replace_all<F>(&self, text:&str, mut replacement:F) -> String where F: FnMut(&str) -> String {
+        let kind:MatchKind = if self.kind == MatchKind::Overlapping { MatchKind::LeftmostLongest } else { self.kind };
+
+        let mut res:String = String::with_capacity(text.len());
+        let mut last:usize = 0;
+        for (start, end, word) in self.find_iter_kind(text, kind) {
+            res.push_str(&text[last..start]);
+            res.push_str(&replacement(word));
+            last = end;
+        }
+        res.push_str(&text[last..]);
+
+        res
+    }
+
+    fn find_iter_kind<'m, 't>(&'m self, text:&'t str, kind:MatchKind) -> FindIter<'m, 't> {
+        FindIter { matcher: self, kind, text, pos: 0, state: ROOT, offsets: VecDeque::new(), pending: VecDeque::new() }
+    }
+
+    fn fold(&self, ch:char) -> char {
+        if self.case_insensitive { fold(ch) } else { ch }
+    }
+
+    // Every word with its insertion rank, lowercased with `fold`
+    fn folded_words(&self, state:usize, word:&mut String, res:&mut Vec<(usize, String)>) {
+        if self.states[state].word_len.is_some() { res.push((self.states[state].order, word.clone())); }
+
+        for (ch, next) in self.states[state].next.iter() {
+            word.push(fold(*ch));
+            self.folded_words(*next, word, res);
+            word.pop();
+        }
+    }
+
+    // A match stands on its own if it isn't glued to a word char on either side
+    fn is_whole_word(&self, text:&str, start:usize, end:usize) -> bool {
+        if !self.whole_words { return true; }
+
+        let is_word_char = |ch:char| ch.is_alphanumeric() || ch == '_';
+        !text[..start].chars().next_back().is_some_and(is_word_char) && !text[end..].chars().next().is_some_and(is_word_char)
+    }
+
+    // Follow the failure links until a state has a transition on `ch`
+    fn step(&self, mut state:usize, ch:char) -> usize {
+        loop {
+            if let Some(next) = self.states[state].next.get(&ch) { return *next; }
+            if state == ROOT { return ROOT; }
+            state = self.states[state].fail;
+        }
+    }
+
+    // Push every word ending at `end` once `state` is reached, longest first. `offsets` holds the byte offsets of the
+    // last chars read, the last one being the start of the char ending at `end`
+    fn outputs(&self, text:&str, state:usize, offsets:&VecDeque<usize>, end:usize, found:&mut VecDeque<Found>) {
+        let mut cur:Option<usize> = if self.states[state].word_len.is_some() { Some(state) } else { self.states[state].output };
+
+        while let Some(id) = cur {
+            let len:usize = self.states[id].word_len.unwrap_or_default();
+            let start:usize = offsets[offsets.len() - len];
+
+            if self.is_whole_word(text, start, end) { found.push_back((start, end, id)); }
+            cur = self.states[id].output;
+        }
+    }
+}
+
+impl From<&Trie> for Matcher {
+    fn from(value: &Trie) -> Self {
+        let mut states:Vec<State> = vec![State { next: BTreeMap::new(), fail: ROOT, word_len: None, order: 0, output: None }];
+        let mut max_len:usize = 0;
+
+        // Breadth first, so the failure link of a state always points to an already finished one
+        let mut queue:VecDeque<(NodeRef, usize, usize)> = VecDeque::from([(value.root.clone(), ROOT, 0)]);
+        while let Some((node, id, depth)) = queue.pop_front() {
+            for (ch, child) in node.as_ref().borrow().sorted_children() {
+                let child_id:usize = states.len();
+                let child_depth:usize = depth + 1;
+
+                let word_len:Option<usize> = child.as_ref().borrow().is_end_of_word.then_some(child_depth);
+                let order:usize = child.as_ref().borrow().order;
+                if word_len.is_some() { max_len = max_len.max(child_depth); }
+
+                let fail:usize = if id == ROOT { ROOT } else {
+                    let mut link:usize = states[id].fail;
+                    loop {
+                        if let Some(next) = states[link].next.get(&ch) { break *next; }
+                        if link == ROOT { break ROOT; }
+                        link = states[link].fail;
+                    }
+                };
+                let output:Option<usize> = if states[fail].word_len.is_some() { Some(fail) } else { states[fail].output };
+
+                states.push(State { next: BTreeMap::new(), fail, word_len, order, output });
+                states[id].next.insert(ch, child_id);
+                queue.push_back((child, child_id, child_depth));
+            }
+        }
+
+        Self { states, kind: MatchKind::default(), case_insensitive: false, whole_words: false, max_len }
+    }
+}
+
+/// Iterator over the words found in a text, created by `Matcher::find_iter`
+pub struct FindIter<'m, 't> {
+    matcher:&'m Matcher,
+    kind:MatchKind,
+    text:&'t str,
+    // Byte offset of the next char to read
+    pos:usize,
+    state:usize,
+    // Byte offsets of the last chars read, as many as the longest word has
+    offsets:VecDeque<usize>,
+    // Matches already found but not yet returned, used in overlapping mode
+    pending:VecDeque<Found>,
+}
+
+// A match as `(start, end, state)`, the state being the one where the word ends
+type Found = (usize, usize, usize);
+
+impl<'t> Iterator for FindIter<'_, 't> {
+    type Item = (usize, usize, &'t str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (start, end, _) = match self.kind {
+            MatchKind::Overlapping => self.next_overlapping()?,
+            MatchKind::LeftmostLongest | MatchKind::LeftmostFirst => self.next_leftmost()?,
+        };
+        Some((start, end, &self.text[start..end]))
+    }
+}
+
+impl FindIter<'_, '_> {
+    fn next_overlapping(&mut self) -> Option<Found> {
+        while self.pending.is_empty() {
+            let ch:char = self.text[self.pos..].chars().next()?;
+            remember(&mut self.offsets, self.pos, self.matcher.max_len);
+            self.pos += ch.len_utf8();
+
+            self.state = self.matcher.step(self.state, self.matcher.fold(ch));
+            self.matcher.outputs(self.text, self.state, &self.offsets, self.pos, &mut self.pending);
+        }
+        self.pending.pop_front()
+    }
+
+    // Read from the end of the previous match with a fresh state, keeping the best match seen so far. Once the text
+    // read goes past the longest word starting at the best match, nothing starting earlier can show up anymore
+    fn next_leftmost(&mut self) -> Option<Found> {
+        let mut best:Option<Found> = None;
+        let mut state:usize = ROOT;
+        let mut found:VecDeque<Found> = VecDeque::new();
+
+        self.offsets.clear();
+
+        for (i, ch) in self.text[self.pos..].char_indices() {
+            let begin:usize = self.pos + i;
+            // The longest word starting at the best match has been fully read
+            if let Some((start, _, _)) = best && self.offsets.len() == self.matcher.max_len && self.offsets[0] > start {
+                break;
+            }
+            remember(&mut self.offsets, begin, self.matcher.max_len);
+
+            state = self.matcher.step(state, self.matcher.fold(ch));
+            self.matcher.outputs(self.text, state, &self.offsets, begin + ch.len_utf8(), &mut found);
+
+            for (start, end, id) in found.drain(..) {
+                let states:&[State] = &self.matcher.states;
+                best = match best {
+                    None => Some((start, end, id)),
+                    Some((s, _, _)) if start < s => Some((start, end, id)),
+                    Some((s, e, _)) if start == s && end > e && self.kind == MatchKind::LeftmostLongest => {
+                        Some((start, end, id))
+                    },
+                    Some((s, _, b)) if start == s && states[id].order < states[b].order
+                        && self.kind == MatchKind::LeftmostFirst => Some((start, end, id)),
+                    _ => best,
+                };
+            }
+        }
+
+        let (start, end, id) = best?;
+        self.pos = end;
+        Some((start, end, id))
+    }
+}
+
+// Lowercase `ch`, chars that lowercase to several chars are kept as they are so that lengths don't change
+fn fold(ch:char) -> char {
+    let mut lower = ch.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(single), None) => single,
+        _ => ch,
+    }
+}
+
+fn remember(offsets:&mut VecDeque<usize>, offset:usize, max:usize) {
+    if max == 0 { return; }
+    if offsets.len() == max { offsets.pop_front(); }
+    offsets.push_back(offset);
+}
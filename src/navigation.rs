@@ -0,0 +1,97 @@
+use crate::{rank::{Rankable, rank_of}, tree::Trie};
+
+/// Navigation over the words of the trie in alphabetical order. The word used to navigate doesn't have to be in the
+/// trie, every query walks down from the root twice at most: once to find the position of the word, once to fetch
+/// the word at the neighbouring position.
+pub trait Navigable {
+    /// Alphabetically first word of the trie
+    /// # Examples
+    /// ```
+    /// # use prefix::{navigation::Navigable, tree::Trie};
+    /// let trie:Trie = Trie::from(["bee", "an", "and"]);
+    ///
+    /// assert_eq!(trie.first(), Some("an".to_string()));
+    /// assert_eq!(trie.last(), Some("bee".to_string()));
+    /// ```
+    fn first(&self) -> Option<String>;
+
+    /// Alphabetically last word of the trie
+    fn last(&self) -> Option<String>;
+
+    /// Smallest word strictly greater than `word`
+    /// # Examples
+    /// ```
+    /// # use prefix::{navigation::Navigable, tree::Trie};
+    /// let trie:Trie = Trie::from(["bee", "an", "and"]);
+    ///
+    /// assert_eq!(trie.successor("an"), Some("and".to_string()));
+    /// assert_eq!(trie.successor("ann"), Some("bee".to_string()));
+    /// assert_eq!(trie.successor("bee"), None);
+    /// ```
+    fn successor(&self, word:&str) -> Option<String>;
+
+    /// Greatest word strictly smaller than `word`
+    /// # Examples
+    /// ```
+    /// # use prefix::{navigation::Navigable, tree::Trie};
+    /// let trie:Trie = Trie::from(["bee", "an", "and"]);
+    ///
+    /// assert_eq!(trie.predecessor("bee"), Some("and".to_string()));
+    /// assert_eq!(trie.predecessor("b"), Some("and".to_string()));
+    /// assert_eq!(trie.predecessor("an"), None);
+    /// ```
+    fn predecessor(&self, word:&str) -> Option<String>;
+
+    /// Smallest word greater than or equal to `word`
+    /// # Examples
+    /// ```
+    /// # use prefix::{navigation::Navigable, tree::Trie};
+    /// let trie:Trie = Trie::from(["bee", "an", "and"]);
+    ///
+    /// assert_eq!(trie.ceiling("and"), Some("and".to_string()));
+    /// assert_eq!(trie.ceiling("b"), Some("bee".to_string()));
+    /// ```
+    fn ceiling(&self, word:&str) -> Option<String>;
+
+    /// Greatest word smaller than or equal to `word`
+    /// # Examples
+    /// ```
+    /// # use prefix::{navigation::Navigable, tree::Trie};
+    /// let trie:Trie = Trie::from(["bee", "an", "and"]);
+    ///
+    /// assert_eq!(trie.floor("and"), Some("and".to_string()));
+    /// assert_eq!(trie.floor("az"), Some("and".to_string()));
+    /// assert_eq!(trie.floor("a"), None);
+    /// ```
+    fn floor(&self, word:&str) -> Option<String>;
+}
+
+impl Navigable for Trie {
+    fn first(&self) -> Option<String> {
+        self.nth(0)
+    }
+
+    fn last(&self) -> Option<String> {
+        self.nth(self.words.checked_sub(1)?)
+    }
+
+    fn successor(&self, word:&str) -> Option<String> {
+        let (rank, found) = rank_of(self, word);
+        self.nth(rank + usize::from(found))
+    }
+
+    fn predecessor(&self, word:&str) -> Option<String> {
+        self.nth(self.rank(word).checked_sub(1)?)
+    }
+
+    fn ceiling(&self, word:&str) -> Option<String> {
+        self.nth(self.rank(word))
+    }
+
+    fn floor(&self, word:&str) -> Option<String> {
+        let (rank, found) = rank_of(self, word);
+        if found { return Some(word.to_string()); }
+
+        self.nth(rank.checked_sub(1)?)
+    }
+}
@@ -0,0 +1,103 @@
+use std::collections::{BTreeMap, HashMap};
+
+/// Weight given to the score of a shorter context each time a longer one has not been seen, as in stupid back-off
+const BACK_OFF:f64 = 0.4;
+
+/// Trie whose keys are sequences of words, counting every sequence of 1 to `order` words seen in a corpus, to predict
+/// the word coming next.
+///
+/// # Examples
+/// ```
+/// # use prefix::ngram::NgramTrie;
+/// let mut ngrams:NgramTrie = NgramTrie::new(3);
+/// ngrams.add_sentence(&["i", "want", "to", "go"]);
+/// ngrams.add_sentence(&["i", "want", "to", "eat"]);
+/// ngrams.add_sentence(&["you", "want", "to", "eat"]);
+///
+/// assert_eq!(ngrams.count(&["want", "to"]), 3);
+/// assert_eq!(ngrams.predict_next(&["i", "want", "to"], 2), vec!["eat", "go"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct NgramTrie {
+    order:usize,
+    root:Gram,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Gram {
+    // Number of times the sequence of words leading here was seen, at the root the number of words seen
+    count:usize,
+    next:BTreeMap<String, Gram>,
+}
+
+impl NgramTrie {
+    /// Create an empty `NgramTrie` counting sequences of up to `order` words, at least 1
+    pub fn new(order:usize) -> Self {
+        Self { order: order.max(1), root: Gram::default() }
+    }
+
+    /// Longest sequence of words counted
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    /// Count every sequence of 1 to `order` words of the sentence. Sequences never span two sentences
+    /// # Time Complexity
+    /// Takes <i>O</i>(words * order) time
+    pub fn add_sentence(&mut self, words:&[&str]) {
+        for start in 0..words.len() {
+            self.root.count += 1;
+
+            let mut cur:&mut Gram = &mut self.root;
+            for word in words[start..].iter().take(self.order) {
+                cur = cur.next.entry(word.to_string()).or_default();
+                cur.count += 1;
+            }
+        }
+    }
+
+    /// Number of times the sequence of words was seen, the number of words seen for an empty sequence
+    pub fn count(&self, words:&[&str]) -> usize {
+        self.go_to(words).map_or(0, |gram| gram.count)
+    }
+
+    /// Get the `k` words most likely to follow `context`, most likely first and ties in alphabetical order. Only the
+    /// last `order - 1` words of the context matter; when they were never followed by a word, shorter contexts are
+    /// tried, each scoring `0.4` times less, down to the plain word frequencies.
+    /// # Time Complexity
+    /// Takes <i>O</i>(order * candidates) time, the shorter contexts are only read until they can no longer change the
+    /// `k` best words
+    pub fn predict_next(&self, context:&[&str], k:usize) -> Vec<String> {
+        if k == 0 { return Vec::new(); }
+        let longest:usize = context.len().min(self.order - 1);
+
+        let mut scores:HashMap<&str, f64> = HashMap::new();
+        let mut weight:f64 = 1.0;
+        for len in (0..=longest).rev() {
+            // Nothing found past this point can beat the k best words already scored
+            if scores.len() >= k && kth_best(&scores, k) >= weight { break; }
+
+            let Some(gram) = self.go_to(&context[context.len() - len..]) else { continue; };
+            if gram.next.is_empty() { continue; }
+
+            for (word, next) in gram.next.iter() {
+                scores.entry(word).or_insert(weight * next.count as f64 / gram.count as f64);
+            }
+            weight *= BACK_OFF;
+        }
+
+        let mut res:Vec<(&str, f64)> = scores.into_iter().collect();
+        res.sort_unstable_by(|(a, sa), (b, sb)| sb.total_cmp(sa).then_with(|| a.cmp(b)));
+        res.into_iter().take(k).map(|(word, _)| word.to_string()).collect()
+    }
+
+    fn go_to(&self, words:&[&str]) -> Option<&Gram> {
+        words.iter().try_fold(&self.root, |gram, word| gram.next.get(*word))
+    }
+}
+
+fn kth_best(scores:&HashMap<&str, f64>, k:usize) -> f64 {
+    let mut best:Vec<f64> = scores.values().copied().collect();
+    best.sort_unstable_by(|a, b| b.total_cmp(a));
+    best[k - 1]
+}
@@ -1,11 +1,11 @@
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
-
-pub(crate) const ALPHABET_SIZE:usize = 26;
+use std::{cell::RefCell, collections::BTreeMap, fmt::Debug, rc::Rc};
 
 pub type NodeRef = Rc<RefCell<Node>>;
 
 pub trait NodeRefOps {
     fn preorder(&self) -> Vec<String>;
+    fn collect_sorted(&self, prefix:&mut String, words:&mut Vec<String>);
+    fn deep_clone(&self) -> NodeRef;
 }
 
 impl NodeRefOps for NodeRef {
@@ -38,32 +38,72 @@ impl NodeRefOps for NodeRef {
                 words.push(word.clone());
             }
 
-            for (_, child) in node.get_children() {
+            for child in node.get_children().values() {
                 stack.push( (cur_lvl + 1, child.clone()) );
             }
             cur_lvl += 1;
         }
         words
     }
+
+    /// Collect, in alphabetical order, all the words below this node, each one prepended with `prefix`.
+    /// The node's own `val` is expected to already be the last char of `prefix`
+    fn collect_sorted(&self, prefix:&mut String, words:&mut Vec<String>) {
+        let node = self.as_ref().borrow();
+        if node.is_end_of_word { words.push(prefix.clone()); }
+
+        for (ch, child) in node.sorted_children() {
+            prefix.push(ch);
+            child.collect_sorted(prefix, words);
+            prefix.pop();
+        }
+    }
+
+    /// Copy the whole subtree into newly allocated nodes, nothing is shared with the original
+    fn deep_clone(&self) -> NodeRef {
+        let node = self.as_ref().borrow();
+
+        let mut copy:Node = Node::new(node.val, node.is_end_of_word);
+        copy.words = node.words;
+        copy.frequency = node.frequency;
+        copy.below = node.below;
+        copy.order = node.order;
+        for (ch, child) in node.get_children() {
+            copy.children.insert(*ch, child.deep_clone());
+        }
+        copy.into_ref()
+    }
 }
 
-#[derive(Debug)]
 pub struct Node {
     pub val:char,
-    children:HashMap<char, NodeRef>,
-    pub is_end_of_word:bool
+    /// Ordered by char, so traversals visit words alphabetically
+    children:BTreeMap<char, NodeRef>,
+    pub is_end_of_word:bool,
+    /// Number of words that end at this node or below it
+    pub words:usize,
+    /// Number of times the word ending at this node was inserted
+    pub frequency:usize,
+    /// Chars of the words below this node, one bit per class of chars, see `char_bit`
+    pub(crate) below:u64,
+    /// Rank of the word ending at this node among all the words inserted in the trie, in the order they first were
+    pub(crate) order:usize,
 }
 
 impl Node {
     pub fn new(val:char, is_end_of_word:bool) -> Self {
         Self { 
             val,
-            children: HashMap::with_capacity(ALPHABET_SIZE / 2), 
-            is_end_of_word
+            children: BTreeMap::new(), 
+            is_end_of_word,
+            words: 0,
+            frequency: 0,
+            below: 0,
+            order: 0,
         }
     }
 
-    pub fn to_ref(self) -> NodeRef {
+    pub fn into_ref(self) -> NodeRef {
         Rc::new(RefCell::new(self))
     }
 
@@ -71,16 +111,23 @@ impl Node {
         self.children.len()
     }
 
-    pub fn get_children(&self) -> &HashMap<char, NodeRef> {
+    pub fn get_children(&self) -> &BTreeMap<char, NodeRef> {
         &self.children
     }
 
+    /// Children ordered by their char, cloned so that the node doesn't have to stay borrowed
+    pub fn sorted_children(&self) -> Vec<(char, NodeRef)> {
+        self.children.iter()
+            .map(|(ch, node)| (*ch, node.clone()))
+            .collect()
+    }
+
     pub fn get_child(&self, c:char) -> Option<&NodeRef> {        
         self.children.get(&c)
     }
 
     pub fn new_child(&mut self, c:char, is_end_of_word:bool) -> NodeRef {
-        let new:NodeRef = Self::new(c, is_end_of_word).to_ref();
+        let new:NodeRef = Self::new(c, is_end_of_word).into_ref();
         self.children.insert(c, new.clone());
 
         new
@@ -90,4 +137,41 @@ impl Node {
         self.children.remove(&c);
     }
 
-}
\ No newline at end of file
+    /// Rebuild the chars found below this node from its children
+    pub(crate) fn update_below(&mut self) {
+        self.below = self.children.iter()
+            .fold(0, |below, (ch, child)| below | char_bit(*ch) | child.as_ref().borrow().below);
+    }
+
+}
+
+/// Shows only the chars of the children instead of the whole subtree
+impl Debug for Node {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let children:Vec<char> = self.sorted_children().into_iter().map(|(ch, _)| ch).collect();
+
+        f.debug_struct("Node")
+            .field("val", &self.val)
+            .field("is_end_of_word", &self.is_end_of_word)
+            .field("words", &self.words)
+            .field("frequency", &self.frequency)
+            .field("children", &children)
+            .finish()
+    }
+}
+
+/// Lowercase a char, keeping the first char when it lowercases to several
+pub(crate) fn fold_case(ch:char) -> char {
+    ch.to_lowercase().next().unwrap_or(ch)
+}
+
+/// Bit of the class of a char once its case is folded: one per ASCII letter and digit, the other chars share the
+/// remaining bits
+pub(crate) fn char_bit(ch:char) -> u64 {
+    let ch:char = fold_case(ch);
+    match ch {
+        'a'..='z' => 1 << (ch as u32 - 'a' as u32),
+        '0'..='9' => 1 << (26 + ch as u32 - '0' as u32),
+        _ => 1 << (36 + ch as u32 % 28),
+    }
+}
@@ -0,0 +1,145 @@
+use std::{collections::HashSet, rc::Rc};
+
+use crate::{node::NodeRef, tree::Trie};
+
+pub trait PatternMatchable {
+    /// Get all the words that match a wildcard `pattern`, sorted alphabetically. Supported syntax:
+    /// - `?` any single char
+    /// - `*` any run of chars, empty included
+    /// - `[abc]`, `[a-z]` one char from the class, `[!abc]` or `[^abc]` one char not in the class
+    /// - `\` matches the next char literally
+    ///
+    /// A `[` without a closing `]` is matched literally.
+    /// # Examples
+    /// ```
+    /// # use prefix::{pattern::PatternMatchable, tree::Trie};
+    /// let trie:Trie = Trie::from(["cat", "cot", "cut", "coat", "being", "bring", "bed"]);
+    ///
+    /// assert_eq!(trie.matches("c?t"), vec!["cat", "cot", "cut"]);
+    /// assert_eq!(trie.matches("b*ing"), vec!["being", "bring"]);
+    /// assert_eq!(trie.matches("c[aeiou]t"), vec!["cat", "cot", "cut"]);
+    /// assert_eq!(trie.matches("c[!a]*t"), vec!["coat", "cot", "cut"]);
+    /// ```
+    /// # Time Complexity
+    /// Visits only the branches that can still match, each node at most once per pattern token
+    fn matches(&self, pattern:&str) -> Vec<String>;
+}
+
+impl PatternMatchable for Trie {
+    fn matches(&self, pattern:&str) -> Vec<String> {
+        let tokens:Vec<Token> = parse(pattern);
+
+        let mut res:Vec<String> = Vec::with_capacity(8);
+        let mut visited:HashSet<(*const (), usize)> = HashSet::new();
+        search(&self.root, 0, &tokens, &mut String::with_capacity(8), &mut visited, &mut res);
+
+        // `*` branches can reach the words out of order
+        res.sort();
+        res
+    }
+}
+
+enum Token {
+    Char(char),
+    Any,
+    Star,
+    Class { negated:bool, ranges:Vec<(char, char)> },
+}
+
+impl Token {
+    fn accepts(&self, ch:char) -> bool {
+        match self {
+            Token::Char(c) => *c == ch,
+            Token::Any | Token::Star => true,
+            Token::Class { negated, ranges } => {
+                ranges.iter().any(|(from, to)| (*from..=*to).contains(&ch)) != *negated
+            },
+        }
+    }
+}
+
+fn parse(pattern:&str) -> Vec<Token> {
+    let chars:Vec<char> = pattern.chars().collect();
+    let mut tokens:Vec<Token> = Vec::with_capacity(chars.len());
+
+    let mut i:usize = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '?' => tokens.push(Token::Any),
+            // Consecutive stars match the same as a single one
+            '*' => if !matches!(tokens.last(), Some(Token::Star)) { tokens.push(Token::Star); },
+            '\\' if i + 1 < chars.len() => {
+                i += 1;
+                tokens.push(Token::Char(chars[i]));
+            },
+            '[' => match parse_class(&chars[i + 1..]) {
+                Some((token, len)) => {
+                    tokens.push(token);
+                    i += len;
+                },
+                None => tokens.push(Token::Char('[')),
+            },
+            ch => tokens.push(Token::Char(ch)),
+        }
+        i += 1;
+    }
+    tokens
+}
+
+// Parse the inside of a class, `chars` starts right after the `[`. Returns the class and the number of chars it
+// took, closing `]` included, or `None` if the class is never closed
+fn parse_class(chars:&[char]) -> Option<(Token, usize)> {
+    let mut i:usize = 0;
+
+    let negated:bool = matches!(chars.first(), Some('!' | '^'));
+    if negated { i += 1; }
+
+    let mut ranges:Vec<(char, char)> = Vec::with_capacity(4);
+    // A `]` right at the start is part of the class
+    let start:usize = i;
+    while i < chars.len() {
+        let ch:char = chars[i];
+        if ch == ']' && i > start { return Some((Token::Class { negated, ranges }, i + 1)); }
+
+        if i + 2 < chars.len() && chars[i + 1] == '-' && chars[i + 2] != ']' {
+            ranges.push((ch, chars[i + 2]));
+            i += 3;
+        } else {
+            ranges.push((ch, ch));
+            i += 1;
+        }
+    }
+    None
+}
+
+fn search(node:&NodeRef, idx:usize, tokens:&[Token], word:&mut String, visited:&mut HashSet<(*const (), usize)>,
+    res:&mut Vec<String>) {
+    // The same node can be reached again at the same token through different `*` expansions
+    if !visited.insert((Rc::as_ptr(node) as *const (), idx)) { return; }
+
+    let Some(token) = tokens.get(idx) else {
+        if node.as_ref().borrow().is_end_of_word { res.push(word.clone()); }
+        return;
+    };
+
+    if let Token::Star = token { search(node, idx + 1, tokens, word, visited, res); }
+    let next:usize = if let Token::Star = token { idx } else { idx + 1 };
+
+    if let Token::Char(ch) = token {
+        let ochild:Option<NodeRef> = node.as_ref().borrow().get_child(*ch).cloned();
+        let Some(child) = ochild else { return; };
+
+        word.push(*ch);
+        search(&child, next, tokens, word, visited, res);
+        word.pop();
+        return;
+    }
+
+    for (ch, child) in node.as_ref().borrow().sorted_children() {
+        if !token.accepts(ch) { continue; }
+
+        word.push(ch);
+        search(&child, next, tokens, word, visited, res);
+        word.pop();
+    }
+}
@@ -0,0 +1,152 @@
+use std::ops::{Bound, RangeBounds};
+
+use crate::{node::NodeRef, tree::Trie};
+
+pub trait Rankable {
+    /// Get the word at position `k` (starting from 0) in alphabetical order, `None` if the trie holds `k` words or
+    /// less
+    /// # Examples
+    /// ```
+    /// # use prefix::{rank::Rankable, tree::Trie};
+    /// let trie:Trie = Trie::from(["bee", "an", "and", "ant"]);
+    ///
+    /// assert_eq!(trie.nth(0), Some("an".to_string()));
+    /// assert_eq!(trie.nth(3), Some("bee".to_string()));
+    /// assert_eq!(trie.nth(4), None);
+    /// ```
+    /// # Time Complexity
+    /// Takes <i>O</i>(word * alphabet) time
+    fn nth(&self, k:usize) -> Option<String>;
+
+    /// Get the alphabetical position of `word`, that is the number of words in the trie that come before it. The word
+    /// doesn't have to be in the trie.
+    /// # Examples
+    /// ```
+    /// # use prefix::{rank::Rankable, tree::Trie};
+    /// let trie:Trie = Trie::from(["bee", "an", "and", "ant"]);
+    ///
+    /// assert_eq!(trie.rank("and"), 1);
+    /// assert_eq!(trie.rank("b"), 3);
+    /// assert_eq!(trie.rank("zebra"), 4);
+    /// ```
+    /// # Time Complexity
+    /// Takes <i>O</i>(word * alphabet) time
+    fn rank(&self, word:&str) -> usize;
+
+    /// Get the words whose alphabetical position falls into `range`, in alphabetical order
+    /// # Examples
+    /// ```
+    /// # use prefix::{rank::Rankable, tree::Trie};
+    /// let trie:Trie = Trie::from(["bee", "an", "and", "ant"]);
+    ///
+    /// assert_eq!(trie.range(1..3), vec!["and", "ant"]);
+    /// assert_eq!(trie.range(2..), vec!["ant", "bee"]);
+    /// ```
+    /// # Time Complexity
+    /// Takes <i>O</i>(range + depth * alphabet) time, subtrees before the range are skipped by their word count
+    fn range<R: RangeBounds<usize>>(&self, range:R) -> Vec<String>;
+}
+
+impl Rankable for Trie {
+    fn nth(&self, mut k:usize) -> Option<String> {
+        if k >= self.words { return None; }
+
+        let mut cur:NodeRef = self.root.clone();
+        let mut word:String = String::with_capacity(8);
+
+        'descend: loop {
+            if cur.as_ref().borrow().is_end_of_word {
+                if k == 0 { return Some(word); }
+                k -= 1;
+            }
+
+            let children:Vec<(char, NodeRef)> = cur.as_ref().borrow().sorted_children();
+            for (ch, child) in children {
+                let count:usize = child.as_ref().borrow().words;
+                if k < count {
+                    word.push(ch);
+                    cur = child;
+                    continue 'descend;
+                }
+                k -= count;
+            }
+            // Counts are out of sync with the nodes
+            return None;
+        }
+    }
+
+    fn rank(&self, word:&str) -> usize {
+        rank_of(self, word).0
+    }
+
+    fn range<R: RangeBounds<usize>>(&self, range:R) -> Vec<String> {
+        let start:usize = match range.start_bound() {
+            Bound::Included(s) => *s,
+            Bound::Excluded(s) => s.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end:usize = match range.end_bound() {
+            Bound::Included(e) => e.saturating_add(1),
+            Bound::Excluded(e) => *e,
+            Bound::Unbounded => self.words,
+        }.min(self.words);
+
+        if start >= end { return Vec::new(); }
+
+        let mut res:Vec<String> = Vec::with_capacity(end - start);
+        let (mut skip, mut take) = (start, end - start);
+        collect_range(&self.root, &mut String::with_capacity(8), &mut skip, &mut take, &mut res);
+
+        res
+    }
+}
+
+// In-order traversal that jumps over whole subtrees while `skip` is larger than their word count
+fn collect_range(node:&NodeRef, prefix:&mut String, skip:&mut usize, take:&mut usize, res:&mut Vec<String>) {
+    if node.as_ref().borrow().is_end_of_word {
+        if *skip > 0 {
+            *skip -= 1;
+        } else {
+            res.push(prefix.clone());
+            *take -= 1;
+        }
+    }
+
+    for (ch, child) in node.as_ref().borrow().sorted_children() {
+        if *take == 0 { return; }
+
+        let count:usize = child.as_ref().borrow().words;
+        if *skip >= count {
+            *skip -= count;
+            continue;
+        }
+
+        prefix.push(ch);
+        collect_range(&child, prefix, skip, take, res);
+        prefix.pop();
+    }
+}
+
+// Rank of `word` along with whether it is in the trie, both found in the same walk down
+pub(crate) fn rank_of(trie:&Trie, word:&str) -> (usize, bool) {
+    let mut rank:usize = 0;
+    let mut cur:NodeRef = trie.root.clone();
+
+    for ch in word.chars() {
+        let ochild:Option<NodeRef> = {
+            let node = cur.as_ref().borrow();
+            // A word ending here is a proper prefix of `word`, so it comes first
+            if node.is_end_of_word { rank += 1; }
+
+            for child in node.get_children().range(..ch).map(|(_, child)| child) {
+                rank += child.as_ref().borrow().words;
+            }
+            node.get_child(ch).cloned()
+        };
+
+        let Some(child) = ochild else { return (rank, false); };
+        cur = child;
+    }
+    let found:bool = cur.as_ref().borrow().is_end_of_word;
+    (rank, found)
+}
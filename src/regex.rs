@@ -0,0 +1,100 @@
+//! Regular expression search, enabled with the `regex` cargo feature.
+//!
+//! The pattern is compiled to a DFA which is run along the branches of the trie, one char at a time. A branch is
+//! abandoned as soon as the DFA reaches its dead state, so only the part of the trie that can still match is visited.
+
+use regex_automata::{
+    Anchored, MatchKind,
+    dfa::{Automaton, dense},
+    util::{primitives::StateID, start},
+};
+
+pub use regex_automata::dfa::dense::BuildError;
+
+use crate::{node::NodeRef, tree::Trie};
+
+pub trait RegexSearchable {
+    /// Lazily get, in alphabetical order, all the words for which `pattern` finds a match. As with
+    /// `Regex::is_match`, the pattern has to be anchored with `^` and `$` to match whole words only.
+    /// # Examples
+    /// ```
+    /// # use prefix::{regex::RegexSearchable, tree::Trie};
+    /// let trie:Trie = Trie::from(["red", "reused", "rewired", "rested", "reed", "tired"]);
+    ///
+    /// let words:Vec<String> = trie.regex_matches(r"^re[a-z]{2,4}ed$").unwrap().collect();
+    /// assert_eq!(words, vec!["rested", "reused", "rewired"]);
+    ///
+    /// let words:Vec<String> = trie.regex_matches("ire").unwrap().collect();
+    /// assert_eq!(words, vec!["rewired", "tired"]);
+    /// ```
+    /// # Errors
+    /// Returns an error if `pattern` is not a valid regular expression or cannot be compiled to a DFA
+    fn regex_matches(&self, pattern:&str) -> Result<RegexMatches, Box<BuildError>>;
+}
+
+impl RegexSearchable for Trie {
+    fn regex_matches(&self, pattern:&str) -> Result<RegexMatches, Box<BuildError>> {
+        // `All` keeps the DFA running past a match, so any match state seen on the way means the word matches
+        let dfa = dense::Builder::new()
+            .configure(dense::Config::new().match_kind(MatchKind::All))
+            .build(pattern)?;
+
+        let start:StateID = dfa.start_state(&start::Config::new().anchored(Anchored::No))
+            .expect("an unanchored start state always exists for a pattern without look-behind");
+
+        let root = Frame { node: self.root.clone(), word: String::new(), state: start, matched: false };
+
+        Ok(RegexMatches { dfa, stack: vec![root] })
+    }
+}
+
+/// Iterator over the words matching a regular expression, created by `RegexSearchable::regex_matches`
+pub struct RegexMatches {
+    dfa:dense::DFA<Vec<u32>>,
+    stack:Vec<Frame>,
+}
+
+struct Frame {
+    node:NodeRef,
+    word:String,
+    state:StateID,
+    // A match was already found along the branch, every word below it matches
+    matched:bool,
+}
+
+impl Iterator for RegexMatches {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(frame) = self.stack.pop() {
+            let node = frame.node.as_ref().borrow();
+
+            // Pushed in reverse so that they are popped alphabetically
+            for (ch, child) in node.get_children().iter().rev() {
+                let mut state:StateID = frame.state;
+                let mut matched:bool = frame.matched;
+
+                if !matched {
+                    let mut buf:[u8; 4] = [0; 4];
+                    for byte in ch.encode_utf8(&mut buf).bytes() {
+                        state = self.dfa.next_state(state, byte);
+                        matched |= self.dfa.is_match_state(state);
+                    }
+                    if !matched && self.dfa.is_dead_state(state) { continue; }
+                }
+
+                let mut word:String = String::with_capacity(frame.word.len() + ch.len_utf8());
+                word.push_str(&frame.word);
+                word.push(*ch);
+
+                self.stack.push(Frame { node: child.clone(), word, state, matched });
+            }
+
+            if !node.is_end_of_word { continue; }
+            if frame.matched || self.dfa.is_match_state(self.dfa.next_eoi_state(frame.state)) {
+                return Some(frame.word);
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,156 @@
+use std::fmt::Write;
+
+use crate::{node::NodeRef, tree::Trie};
+
+pub trait Renderable {
+    /// Render the whole trie as a Graphviz DOT graph. Every node is labeled with its `val`, nodes that end a word
+    /// are double-circled.
+    /// # Examples
+    /// ```
+    /// # use prefix::{render::Renderable, tree::Trie};
+    /// let trie:Trie = Trie::from(["a", "an"]);
+    ///
+    /// let dot:String = trie.to_dot();
+    ///
+    /// assert!(dot.starts_with("digraph trie {"));
+    /// assert!(dot.contains("n2 [label=\"n\", shape=doublecircle];"));
+    /// ```
+    fn to_dot(&self) -> String;
+
+    /// Render as a Graphviz DOT graph only the part of the trie below the node reached by `prefix`, returns `None`
+    /// if the prefix is not in the trie
+    fn subtree_to_dot(&self, prefix:&str) -> Option<String>;
+
+    /// Same as `subtree_to_dot`, `label` is called with every word of the subtree and the returned text (a frequency,
+    /// a value, ...) is shown next to the node that ends the word
+    /// # Examples
+    /// ```
+    /// # use prefix::{render::Renderable, tree::Trie};
+    /// let trie:Trie = Trie::from(["and", "ant"]);
+    ///
+    /// let dot:String = trie.to_dot_labeled("an", |word| Some(word.len().to_string())).unwrap();
+    ///
+    /// assert!(dot.contains("[label=\"d\", shape=doublecircle, xlabel=\"3\"]"));
+    /// ```
+    fn to_dot_labeled<F>(&self, prefix:&str, label:F) -> Option<String> where F: Fn(&str) -> Option<String>;
+
+    /// Render the whole trie as a tree drawn with box-drawing characters, words end at nodes marked with `*`
+    /// # Examples
+    /// ```
+    /// # use prefix::{render::Renderable, tree::Trie};
+    /// let trie:Trie = Trie::from(["an", "at"]);
+    ///
+    /// assert_eq!(trie.to_ascii(), ".\n└── a\n    ├── n *\n    └── t *\n");
+    /// ```
+    fn to_ascii(&self) -> String;
+
+    /// Render as a box-drawing tree only the part of the trie below the node reached by `prefix`, returns `None` if the
+    /// prefix is not in the trie
+    fn subtree_to_ascii(&self, prefix:&str) -> Option<String>;
+}
+
+impl Renderable for Trie {
+    fn to_dot(&self) -> String {
+        self.to_dot_labeled("", |_| None).unwrap_or_default()
+    }
+
+    fn subtree_to_dot(&self, prefix:&str) -> Option<String> {
+        self.to_dot_labeled(prefix, |_| None)
+    }
+
+    fn to_dot_labeled<F>(&self, prefix:&str, label:F) -> Option<String> where F: Fn(&str) -> Option<String> {
+        let start:NodeRef = self.go_to(prefix)?;
+
+        let mut out:String = String::with_capacity(256);
+        out.push_str("digraph trie {\n    node [shape=circle];\n");
+
+        let root_label:&str = if prefix.is_empty() { "root" } else { prefix };
+        let _ = write!(out, "    n0 [label=\"{}\", shape=box", escape(root_label));
+        // The prefix itself may be a word, drawn with a double border
+        if start.as_ref().borrow().is_end_of_word {
+            out.push_str(", peripheries=2");
+            if let Some(text) = label(prefix) {
+                let _ = write!(out, ", xlabel=\"{}\"", escape(&text));
+            }
+        }
+        out.push_str("];\n");
+
+        let mut next_id:usize = 1;
+        let mut word:String = prefix.to_string();
+        dot_children(&start, 0, &mut word, &mut next_id, &label, &mut out);
+
+        out.push_str("}\n");
+        Some(out)
+    }
+
+    fn to_ascii(&self) -> String {
+        self.subtree_to_ascii("").unwrap_or_default()
+    }
+
+    fn subtree_to_ascii(&self, prefix:&str) -> Option<String> {
+        let start:NodeRef = self.go_to(prefix)?;
+
+        let mut out:String = String::with_capacity(256);
+        out.push_str(if prefix.is_empty() { "." } else { prefix });
+        if !prefix.is_empty() && start.as_ref().borrow().is_end_of_word { out.push_str(" *"); }
+        out.push('\n');
+
+        ascii_children(&start, &mut String::new(), &mut out);
+
+        Some(out)
+    }
+}
+
+fn dot_children<F>(node:&NodeRef, id:usize, word:&mut String, next_id:&mut usize, label:&F, out:&mut String)
+where F: Fn(&str) -> Option<String> {
+    for (ch, child) in node.as_ref().borrow().sorted_children() {
+        let child_id:usize = *next_id;
+        *next_id += 1;
+        word.push(ch);
+
+        let _ = write!(out, "    n{} [label=\"{}\"", child_id, escape(&ch.to_string()));
+        if child.as_ref().borrow().is_end_of_word {
+            out.push_str(", shape=doublecircle");
+            if let Some(text) = label(word) {
+                let _ = write!(out, ", xlabel=\"{}\"", escape(&text));
+            }
+        }
+        out.push_str("];\n");
+        let _ = writeln!(out, "    n{} -> n{};", id, child_id);
+
+        dot_children(&child, child_id, word, next_id, label, out);
+        word.pop();
+    }
+}
+
+fn ascii_children(node:&NodeRef, indent:&mut String, out:&mut String) {
+    let children = node.as_ref().borrow().sorted_children();
+    let last:usize = children.len().saturating_sub(1);
+
+    for (i, (ch, child)) in children.into_iter().enumerate() {
+        let (branch, pad) = if i == last { ("└── ", "    ") } else { ("├── ", "│   ") };
+
+        out.push_str(indent);
+        out.push_str(branch);
+        out.push(ch);
+        if child.as_ref().borrow().is_end_of_word { out.push_str(" *"); }
+        out.push('\n');
+
+        indent.push_str(pad);
+        ascii_children(&child, indent, out);
+        indent.truncate(indent.len() - pad.len());
+    }
+}
+
+// Escape text to be used inside a quoted DOT string
+fn escape(text:&str) -> String {
+    let mut res:String = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '"' | '\\' => { res.push('\\'); res.push(ch); },
+            '\n' => res.push_str("\\n"),
+            _ => res.push(ch),
+        }
+    }
+    res
+}
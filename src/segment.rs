@@ -0,0 +1,130 @@
+use std::cmp::Ordering;
+
+use crate::tree::Trie;
+
+/// What makes a segmentation the best one
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SegmentBy {
+    /// As few words as possible, ties broken by frequency
+    #[default]
+    FewestWords,
+    /// Highest product of the word probabilities, frequency divided by the total frequency of the trie, ties broken by
+    /// the number of words
+    Frequency,
+}
+
+pub trait Segmentable {
+    /// Split `text` into words of the trie, returns the best segmentation or `None` if the text cannot be split into
+    /// words of the trie only. The words are slices of `text`.
+    /// # Examples
+    /// ```
+    /// # use prefix::{segment::{SegmentBy, Segmentable}, tree::Trie};
+    /// let trie:Trie = Trie::from(["best", "pizza", "in", "town", "be", "st", "pi", "zza", "tow", "n"]);
+    ///
+    /// assert_eq!(trie.segment("bestpizzaintown", SegmentBy::FewestWords), Some(vec!["best", "pizza", "in", "town"]));
+    /// assert_eq!(trie.segment("bestpizzas", SegmentBy::FewestWords), None);
+    /// ```
+    /// # Time Complexity
+    /// Takes <i>O</i>(text * longest word) time, by dynamic programming over the words starting at each position
+    fn segment<'a>(&self, text:&'a str, by:SegmentBy) -> Option<Vec<&'a str>>;
+
+    /// Get up to `limit` ways of splitting `text` into words of the trie, those starting with longer words first
+    /// # Examples
+    /// ```
+    /// # use prefix::{segment::Segmentable, tree::Trie};
+    /// let trie:Trie = Trie::from(["pen", "pens", "is", "sis", "land", "island"]);
+    ///
+    /// assert_eq!(trie.all_segmentations("penisland", 10), vec![
+    ///     vec!["pen", "island"],
+    ///     vec!["pen", "is", "land"],
+    /// ]);
+    /// ```
+    fn all_segmentations<'a>(&self, text:&'a str, limit:usize) -> Vec<Vec<&'a str>>;
+}
+
+// Score of the best segmentation of a suffix of the text
+#[derive(Clone, Copy)]
+struct Score {
+    words:usize,
+    // Sum of the log probabilities of the words
+    log_prob:f64,
+}
+
+impl Score {
+    fn cmp(&self, other:&Score, by:SegmentBy) -> Ordering {
+        let fewer:Ordering = other.words.cmp(&self.words);
+        let likelier:Ordering = self.log_prob.partial_cmp(&other.log_prob).unwrap_or(Ordering::Equal);
+
+        match by {
+            SegmentBy::FewestWords => fewer.then(likelier),
+            SegmentBy::Frequency => likelier.then(fewer),
+        }
+    }
+}
+
+impl Segmentable for Trie {
+    fn segment<'a>(&self, text:&'a str, by:SegmentBy) -> Option<Vec<&'a str>> {
+        let total:f64 = self.total_frequency().max(1) as f64;
+
+        // best[i]: best score to split text[i..], with the end of its first word
+        let mut best:Vec<Option<(Score, usize)>> = vec![None; text.len() + 1];
+        best[text.len()] = Some((Score { words: 0, log_prob: 0.0 }, text.len()));
+
+        for (i, _) in text.char_indices().rev() {
+            for word in self.prefixes_of(&text[i..]) {
+                let end:usize = i + word.len();
+                let Some((rest, _)) = best[end] else { continue; };
+
+                let probability:f64 = self.frequency(word).max(1) as f64 / total;
+                let score = Score { words: rest.words + 1, log_prob: rest.log_prob + probability.ln() };
+
+                let better:bool = match best[i] {
+                    None => true,
+                    Some((current, _)) => score.cmp(&current, by) == Ordering::Greater,
+                };
+                if better { best[i] = Some((score, end)); }
+            }
+        }
+
+        let mut res:Vec<&str> = Vec::with_capacity(best[0]?.0.words);
+        let mut i:usize = 0;
+        while i < text.len() {
+            let (_, end) = best[i]?;
+            res.push(&text[i..end]);
+            i = end;
+        }
+        Some(res)
+    }
+
+    fn all_segmentations<'a>(&self, text:&'a str, limit:usize) -> Vec<Vec<&'a str>> {
+        // splittable[i]: text[i..] can be split at all, so dead ends are never explored
+        let mut splittable:Vec<bool> = vec![false; text.len() + 1];
+        splittable[text.len()] = true;
+        for (i, _) in text.char_indices().rev() {
+            splittable[i] = self.prefixes_of(&text[i..]).iter().any(|word| splittable[i + word.len()]);
+        }
+
+        let mut res:Vec<Vec<&str>> = Vec::with_capacity(limit.min(8));
+        if limit > 0 && splittable[0] {
+            collect_segmentations(self, text, 0, &splittable, &mut Vec::new(), limit, &mut res);
+        }
+        res
+    }
+}
+
+fn collect_segmentations<'a>(trie:&Trie, text:&'a str, i:usize, splittable:&[bool], cur:&mut Vec<&'a str>,
+    limit:usize, res:&mut Vec<Vec<&'a str>>) {
+    if i == text.len() {
+        res.push(cur.clone());
+        return;
+    }
+
+    for word in trie.prefixes_of(&text[i..]).into_iter().rev() {
+        if res.len() >= limit { return; }
+        if !splittable[i + word.len()] { continue; }
+
+        cur.push(word);
+        collect_segmentations(trie, text, i + word.len(), splittable, cur, limit, res);
+        cur.pop();
+    }
+}
@@ -0,0 +1,138 @@
+//! `serde` support, enabled with the `serde` cargo feature.
+//!
+//! By default a `Trie` is serialized as its sorted list of `(word, frequency)` pairs, a bare word being read back with
+//! a frequency of 1. The nested node structure can be selected per field with
+//! `#[serde(with = "prefix::serialize::nested")]`, the word list explicitly with
+//! `#[serde(with = "prefix::serialize::words")]`. Both keep the frequencies.
+//!
+//! # Examples
+//! ```
+//! # use prefix::tree::Trie;
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Cache {
+//!     words:Trie,
+//!     #[serde(with = "prefix::serialize::nested")]
+//!     shape:Trie,
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer, ser::SerializeStruct};
+
+use crate::{node::Node, tree::Trie};
+
+impl Serialize for Trie {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        words::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Trie {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        words::deserialize(deserializer)
+    }
+}
+
+impl Serialize for Node {
+    /// Serialized as `{ "end": bool, "frequency": usize, "children": { char: Node, .. } }`, children ordered by
+    /// their char
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let children:Vec<(char, _)> = self.sorted_children();
+
+        let mut state = serializer.serialize_struct("Node", 3)?;
+        state.serialize_field("end", &self.is_end_of_word)?;
+        state.serialize_field("frequency", &self.frequency)?;
+        state.serialize_field("children", &ChildrenSer(&children))?;
+        state.end()
+    }
+}
+
+struct ChildrenSer<'a>(&'a [(char, crate::node::NodeRef)]);
+
+impl Serialize for ChildrenSer<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().map(|(ch, node)| (ch, node.as_ref())))
+    }
+}
+
+// Owned mirror of `Node`, only used while deserializing the nested representation
+#[derive(Deserialize)]
+struct NodeRepr {
+    #[serde(default)]
+    end:bool,
+    #[serde(default)]
+    frequency:usize,
+    #[serde(default)]
+    children:BTreeMap<char, NodeRepr>,
+}
+
+impl NodeRepr {
+    // Words are inserted one by one so that every counter kept by `Trie` stays consistent
+    fn insert_into(&self, prefix:&mut String, trie:&mut Trie) {
+        if self.end { trie.insert_with_frequency(prefix, self.frequency.max(1)); }
+
+        for (ch, child) in self.children.iter() {
+            prefix.push(*ch);
+            child.insert_into(prefix, trie);
+            prefix.pop();
+        }
+    }
+}
+
+/// (De)serialize a `Trie` as its sorted list of `(word, frequency)` pairs, this is the default representation.
+pub mod words {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::{autocomplete::collect_with_frequency, tree::Trie};
+
+    // A bare word stands for a frequency of 1
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Entry {
+        Counted(String, usize),
+        Word(String),
+    }
+
+    pub fn serialize<S: Serializer>(trie:&Trie, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut entries:Vec<(String, usize)> = Vec::with_capacity(trie.words);
+        collect_with_frequency(&trie.root, &mut String::with_capacity(8), 0, None, &mut entries);
+
+        serializer.collect_seq(entries)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Trie, D::Error> {
+        let entries:Vec<Entry> = Vec::deserialize(deserializer)?;
+
+        let mut trie:Trie = Trie::new();
+        for entry in entries.iter() {
+            match entry {
+                Entry::Counted(word, frequency) => trie.insert_with_frequency(word, (*frequency).max(1)),
+                Entry::Word(word) => trie.insert(word),
+            }
+        }
+
+        Ok(trie)
+    }
+}
+
+/// (De)serialize a `Trie` as its nested node structure, starting at the root node.
+pub mod nested {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::NodeRepr;
+    use crate::tree::Trie;
+
+    pub fn serialize<S: Serializer>(trie:&Trie, serializer: S) -> Result<S::Ok, S::Error> {
+        trie.root.as_ref().borrow().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Trie, D::Error> {
+        let root:NodeRepr = NodeRepr::deserialize(deserializer)?;
+
+        let mut trie:Trie = Trie::new();
+        root.insert_into(&mut String::with_capacity(8), &mut trie);
+
+        Ok(trie)
+    }
+}
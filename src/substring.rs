@@ -0,0 +1,104 @@
+use std::collections::BTreeSet;
+
+use crate::tree::Trie;
+
+// Separates the words in the text the suffixes are taken from, it sorts before every other char
+const SEPARATOR:char = '\0';
+
+/// Generalized suffix trie answering which words contain a given substring.
+///
+/// Backed by a suffix array: the words are stored once, sorted and joined by a separator, and every suffix of every
+/// word is kept as a single `u32` offset into that text, sorted by the suffix it starts. The suffixes starting with a
+/// substring are then one contiguous block, found with two binary searches.
+///
+/// # Examples
+/// ```
+/// # use prefix::{substring::SuffixTrie, tree::Trie};
+/// let trie:Trie = Trie::from(["through", "tough", "enough", "thought", "cough"]);
+///
+/// let suffixes:SuffixTrie = SuffixTrie::from(&trie);
+///
+/// assert_eq!(suffixes.containing("ough"), vec!["cough", "enough", "thought", "through", "tough"]);
+/// assert_eq!(suffixes.containing("hou"), vec!["thought"]);
+/// ```
+pub struct SuffixTrie {
+    // Sorted unique words, each one followed by the separator
+    text:String,
+    // Offset in `text` where each word starts
+    starts:Vec<u32>,
+    // Offsets of every suffix of every word, sorted by the suffix
+    suffixes:Vec<u32>,
+}
+
+impl SuffixTrie {
+    /// Build from any list of words, duplicates and empty words are ignored, words containing `'\0'` are skipped
+    /// # Panics
+    /// If the words take more than 4GiB in total
+    pub fn from_words<I, S>(words:I) -> Self where I: IntoIterator<Item = S>, S: AsRef<str> {
+        let words:BTreeSet<String> = words.into_iter()
+            .map(|word| word.as_ref().to_string())
+            .filter(|word| !word.is_empty() && !word.contains(SEPARATOR))
+            .collect();
+
+        let mut text:String = String::with_capacity(words.iter().map(|word| word.len() + 1).sum());
+        let mut starts:Vec<u32> = Vec::with_capacity(words.len());
+        let mut suffixes:Vec<u32> = Vec::with_capacity(text.capacity());
+
+        for word in words.iter() {
+            let start:u32 = u32::try_from(text.len()).expect("words must fit in 4GiB");
+            starts.push(start);
+            suffixes.extend(word.char_indices().map(|(i, _)| start + i as u32));
+
+            text.push_str(word);
+            text.push(SEPARATOR);
+        }
+
+        let bytes:&[u8] = text.as_bytes();
+        suffixes.sort_unstable_by(|a, b| bytes[*a as usize..].cmp(&bytes[*b as usize..]));
+
+        Self { text, starts, suffixes }
+    }
+
+    /// Get all the words that contain `substring`, sorted alphabetically. An empty substring matches every word
+    /// # Time Complexity
+    /// Takes <i>O</i>(substring * log n + result) time
+    pub fn containing(&self, substring:&str) -> Vec<&str> {
+        if substring.contains(SEPARATOR) { return Vec::new(); }
+
+        let bytes:&[u8] = self.text.as_bytes();
+        let sub:&[u8] = substring.as_bytes();
+        let suffix = |offset:&u32| &bytes[*offset as usize..];
+
+        let from:usize = self.suffixes.partition_point(|offset| suffix(offset) < sub);
+        let to:usize = from + self.suffixes[from..].partition_point(|offset| suffix(offset).starts_with(sub));
+
+        // Word indexes follow the alphabetical order of the words
+        let found:BTreeSet<usize> = self.suffixes[from..to].iter()
+            .map(|offset| self.starts.partition_point(|start| start <= offset) - 1)
+            .collect();
+
+        found.into_iter().map(|i| self.word(i)).collect()
+    }
+
+    /// Number of words in the suffix trie
+    pub fn len(&self) -> usize {
+        self.starts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.starts.is_empty()
+    }
+
+    fn word(&self, i:usize) -> &str {
+        let start:usize = self.starts[i] as usize;
+        let end:usize = self.starts.get(i + 1).map_or(self.text.len(), |next| *next as usize) - SEPARATOR.len_utf8();
+
+        &self.text[start..end]
+    }
+}
+
+impl From<&Trie> for SuffixTrie {
+    fn from(value: &Trie) -> Self {
+        Self::from_words(value.get_sorted_words())
+    }
+}
@@ -0,0 +1,66 @@
+use crate::tree::{Trie, reverse};
+
+/// Queries on the end of the words. They are answered from the suffix index when it is enabled with
+/// `Trie::enable_suffix_index`, otherwise by going through every word of the trie.
+pub trait SuffixSearchable {
+    /// Get all the words that end with `suffix`, the suffix itself included if it is a word, sorted alphabetically
+    /// # Examples
+    /// ```
+    /// # use prefix::{suffix::SuffixSearchable, tree::Trie};
+    /// let mut trie:Trie = Trie::from(["nation", "motion", "station", "stationary"]);
+    /// trie.enable_suffix_index();
+    ///
+    /// assert_eq!(trie.ends_with("tion"), vec!["motion", "nation", "station"]);
+    /// ```
+    /// # Time Complexity
+    /// Takes <i>O</i>(suffix + result) time with the index, <i>O</i>(n) time without it
+    fn ends_with(&self, suffix:&str) -> Vec<String>;
+
+    /// Get all the words that start with `prefix` and end with `suffix`, sorted alphabetically. With the index the
+    /// side matching fewer words is enumerated and filtered by the other.
+    /// # Examples
+    /// ```
+    /// # use prefix::{suffix::SuffixSearchable, tree::Trie};
+    /// let mut trie:Trie = Trie::from(["nation", "motion", "station", "stationary"]);
+    /// trie.enable_suffix_index();
+    ///
+    /// assert_eq!(trie.starts_with_and_ends_with("st", "ion"), vec!["station"]);
+    /// ```
+    fn starts_with_and_ends_with(&self, prefix:&str, suffix:&str) -> Vec<String>;
+}
+
+impl SuffixSearchable for Trie {
+    fn ends_with(&self, suffix:&str) -> Vec<String> {
+        let Some(reversed) = self.reversed.as_ref() else {
+            return self.get_sorted_words().into_iter().filter(|word| word.ends_with(suffix)).collect();
+        };
+
+        let mut res:Vec<String> = reversed.words_starting_with(&reverse(suffix)).iter()
+            .map(|word| reverse(word))
+            .collect();
+        res.sort();
+
+        res
+    }
+
+    fn starts_with_and_ends_with(&self, prefix:&str, suffix:&str) -> Vec<String> {
+        let matching = |word:&String| word.starts_with(prefix) && word.ends_with(suffix);
+
+        let Some(reversed) = self.reversed.as_ref() else {
+            return self.words_starting_with(prefix).into_iter().filter(matching).collect();
+        };
+
+        let rev_suffix:String = reverse(suffix);
+        if self.count_prefix(prefix) <= reversed.count_prefix(&rev_suffix) {
+            return self.words_starting_with(prefix).into_iter().filter(matching).collect();
+        }
+
+        let mut res:Vec<String> = reversed.words_starting_with(&rev_suffix).iter()
+            .map(|word| reverse(word))
+            .filter(matching)
+            .collect();
+        res.sort();
+
+        res
+    }
+}
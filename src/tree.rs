@@ -1,43 +1,141 @@
 #![allow(dead_code)]
 
-use std::{collections::HashMap, fmt::Display, fs::File, io::{BufRead, BufReader, Read, Seek, SeekFrom}};
+use std::{collections::HashMap, fmt::{Debug, Display}, fs::File, hash::{Hash, Hasher}, io::{BufRead, BufReader, Read, Seek, SeekFrom}};
 
-use super::node::{Node, NodeRef, NodeRefOps};
+use super::{autocomplete::collect_with_frequency, cursor::TrieCursor, matcher::Matcher, ngram::NgramTrie, node::{Node, NodeRef, NodeRefOps, char_bit}, rank::Rankable, render::Renderable, substring::SuffixTrie};
 
 /// Growable prefix tree, written as `Trie`
 /// 
 /// # Examples
 /// 
-/// ``` 
+/// ```
+/// # use prefix::tree::Trie;
 /// let mut trie = Trie::new();
-/// trie.push("first");
-/// trie.push("second");
+/// trie.insert("first");
+/// trie.insert("second");
 /// 
 /// ```
 /// The `trie` macro is provided for convenient initialization:
 /// 
 /// ```
+/// # use prefix::{trie, tree::Trie};
 /// let mut trie1 = trie!["some", "word", "here"];
-/// trie.inser("word2");
+/// trie1.insert("word2");
 /// 
 /// let mut trie2 = Trie::from(["some", "word", "here", "word2"]);
 /// assert_eq!(trie1, trie2)
 /// ```
 ///
+/// Two tries are equal when they hold the same words, no matter the insertion order. Cloning copies every node, so
+/// the clone can be modified without affecting the original.
+///
 /// # Fields
 /// - `words`: Number of words that are in the tree.
 /// - `root`: Root of prefix tree
+/// - `reversed`: Optional suffix index, every word spelled backwards, see `Trie::enable_suffix_index`
+/// - `frequencies`: Sum of the frequencies of all the words
+/// - `insertions`: Number of words inserted so far, used to number the words in insertion order
+/// - `infixes`: Optional infix index, every multi-word entry rotated to start at each of its inner words, see
+///   `Trie::enable_infix_index`
 pub struct Trie {
     pub words:usize,
     pub root:NodeRef,
+    pub(crate) reversed:Option<Box<Trie>>,
+    pub(crate) frequencies:usize,
+    pub(crate) infixes:Option<Box<Trie>>,
+    pub(crate) insertions:usize,
 }
 
+/// Lists the words of the trie in alphabetical order, e.g. `[a, an, and]`. The precision truncates the listing to
+/// that many words, `{:.2}` gives `[a, an, … 1 more]`. The alternate flag, `{:#}`, draws the trie as a tree instead.
 impl Display for Trie {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for (ch, v) in self.get_words() {
-            println!("[ {} | {} ] => {:?}", ch, v.len(), v);
+        if f.alternate() { return f.write_str(&self.to_ascii()); }
+
+        let words:Vec<String> = self.get_sorted_words();
+        let shown:usize = f.precision().unwrap_or(words.len()).min(words.len());
+
+        f.write_str("[")?;
+        for (i, word) in words[..shown].iter().enumerate() {
+            if i > 0 { f.write_str(", ")?; }
+            f.write_str(word)?;
         }
-        write!(f, "")
+        if shown < words.len() {
+            if shown > 0 { f.write_str(", ")?; }
+            write!(f, "… {} more", words.len() - shown)?;
+        }
+        f.write_str("]")
+    }
+}
+
+/// Shows the word count and the first words in alphabetical order, at most `DEBUG_ENTRIES` of them followed by how
+/// many are left out, e.g. `Trie { words: 3, entries: ["a", "an", "and"] }`
+impl Debug for Trie {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let shown:Vec<String> = self.range(..DEBUG_ENTRIES);
+
+        f.debug_struct("Trie")
+            .field("words", &self.words)
+            .field("entries", &Entries { shown: &shown, more: self.words - shown.len() })
+            .finish()
+    }
+}
+
+// Number of words listed by the `Debug` output of a trie
+const DEBUG_ENTRIES:usize = 20;
+
+struct Entries<'a> {
+    shown:&'a [String],
+    more:usize,
+}
+
+impl Debug for Entries<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("[")?;
+        for (i, word) in self.shown.iter().enumerate() {
+            if i > 0 { f.write_str(", ")?; }
+            write!(f, "{word:?}")?;
+        }
+        if self.more > 0 {
+            if !self.shown.is_empty() { f.write_str(", ")?; }
+            write!(f, "… {} more", self.more)?;
+        }
+        f.write_str("]")
+    }
+}
+
+impl Clone for Trie {
+    fn clone(&self) -> Self {
+        Self { 
+            words: self.words, 
+            root: self.root.deep_clone(),
+            reversed: self.reversed.clone(),
+            frequencies: self.frequencies,
+            infixes: self.infixes.clone(),
+            insertions: self.insertions,
+        }
+    }
+}
+
+// Compares the words only, see `Trie::eq_with_frequencies` to compare the frequencies as well
+impl PartialEq for Trie {
+    fn eq(&self, other: &Self) -> bool {
+        self.words == other.words && self.get_sorted_words() == other.get_sorted_words()
+    }
+}
+
+impl Eq for Trie {}
+
+impl Hash for Trie {
+    // Words are hashed in alphabetical order, equal tries hash the same whatever order the words were inserted in
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.get_sorted_words().hash(state);
+    }
+}
+
+impl Default for Trie {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -55,12 +153,17 @@ impl Trie {
     /// 
     /// # Examples
     /// ```
+    /// # use prefix::tree::Trie;
     /// let mut trie:Trie = Trie::new();
     /// ```
     pub fn new() -> Self {
         Self { 
             words:0, 
-            root: Node::new( '\0', false).to_ref()
+            root: Node::new( '\0', false).into_ref(),
+            reversed: None,
+            frequencies: 0,
+            infixes: None,
+            insertions: 0,
         }
     }
     
@@ -68,12 +171,14 @@ impl Trie {
     /// 
     /// ## Example
     /// ```
+    /// # use std::collections::HashMap;
+    /// # use prefix::tree::Trie;
     /// let mut trie:Trie = Trie::new();
     /// 
-    /// trie.inser("this");
-    /// trie.inser("that");
+    /// trie.insert("this");
+    /// trie.insert("that");
     /// 
-    /// let words:Hashmap<char, Vec<String>> = trie.get_words();
+    /// let words:HashMap<char, Vec<String>> = trie.get_words();
     /// ```
     pub fn get_words(&self) -> HashMap<char, Vec<String>> {
         let mut res:HashMap<char, Vec<String>> = HashMap::with_capacity( self.root.as_ref().borrow().children_size() );
@@ -86,13 +191,40 @@ impl Trie {
         res
     }
 
+    /// Get all the words in the trie, sorted alphabetically
+    /// 
+    /// ## Example
+    /// ```
+    /// # use prefix::tree::Trie;
+    /// let trie:Trie = Trie::from(["that", "this", "a"]);
+    /// 
+    /// assert_eq!(trie.get_sorted_words(), vec!["a", "that", "this"]);
+    /// ```
+    pub fn get_sorted_words(&self) -> Vec<String> {
+        let mut words:Vec<String> = Vec::with_capacity(self.words);
+        self.root.collect_sorted(&mut String::with_capacity(8), &mut words);
+
+        words
+    }
+
+    // All the words that start with `prefix`, prefix included, sorted alphabetically
+    pub(crate) fn words_starting_with(&self, prefix:&str) -> Vec<String> {
+        let Some(node) = self.go_to(prefix) else { return Vec::new(); };
+
+        let mut words:Vec<String> = Vec::with_capacity(node.as_ref().borrow().words);
+        node.collect_sorted(&mut prefix.to_string(), &mut words);
+
+        words
+    }
+
     /// Check if the word is in trie, stops as soon as possible if one of the char differs
     /// 
     /// # Examples
     /// ```
+    /// # use prefix::tree::Trie;
     /// let mut trie:Trie = Trie::new();
     /// 
-    /// trie.inser("word");
+    /// trie.insert("word");
     /// 
     /// assert_eq!(trie.contains("word"), true);
     /// ```
@@ -105,55 +237,84 @@ impl Trie {
         false
     }
 
-    /// Insert word into prefix tree, if word is already in the tree ignores it
+    /// Insert word into prefix tree, if word is already in the tree only its frequency grows by one
     /// 
     /// # Examples
     /// ```
+    /// # use prefix::tree::Trie;
     /// let mut trie:Trie = Trie::new();
     /// 
-    /// trie.inser("word");
+    /// trie.insert("word");
     /// ```
     /// # Time Complexity
     /// Takes <i>O</i>(1) time
     pub fn insert(&mut self, word:&str) {
+        self.insert_with_frequency(word, 1);
+    }
+
+    /// Insert word into prefix tree and add `frequency` to the number of times it has been seen, e.g. when building
+    /// from a list of word counts
+    /// 
+    /// # Examples
+    /// ```
+    /// # use prefix::tree::Trie;
+    /// let mut trie:Trie = Trie::new();
+    /// 
+    /// trie.insert_with_frequency("the", 120);
+    /// trie.insert("the");
+    /// 
+    /// assert_eq!(trie.frequency("the"), 121);
+    /// assert_eq!(trie.words, 1);
+    /// ```
+    pub fn insert_with_frequency(&mut self, word:&str, frequency:usize) {
+        if word.is_empty() { return; }
+
+        // Nodes from the root to the parent of the last char, their word counts grow only if the word is new
+        let mut path:Vec<NodeRef> = Vec::with_capacity(word.len() + 1);
         let mut cur:NodeRef = self.root.clone();
-        
-        let mut is_last_char:bool = false;
-        for (i, ch) in word.char_indices() {
-            if i == word.len() - 1 { is_last_char = true; }
-            
-            let ochild:Option<NodeRef> = cur.as_ref().borrow_mut().get_child(ch).cloned();
-            if let Some(node) = ochild { // if current node has the child
-                // println!("{:?}", cur.as_ref().borrow().get_children());
-                if !is_last_char {
-                    cur = node;
-                    continue;
-                }
-                if !node.as_ref().borrow().is_end_of_word {
-                    node.as_ref().borrow_mut().is_end_of_word = true;
-                    self.words += 1;
-                }
-                return;
-            } else { // if current not doesn't have the child
-                // println!("{:?}", cur.as_ref().borrow().get_children());
-                let new_child:NodeRef = cur.as_ref().borrow_mut().new_child(ch, is_last_char);
-                
-                if is_last_char { 
-                    self.words += 1;
-                    return;
-                }
-                cur = new_child;
-            }
+
+        for ch in word.chars() {
+            let ochild:Option<NodeRef> = cur.as_ref().borrow().get_child(ch).cloned();
+            let next:NodeRef = match ochild {
+                Some(node) => node,
+                None => cur.as_ref().borrow_mut().new_child(ch, false),
+            };
+            path.push(cur);
+            cur = next;
+        }
+
+        // Every node on the path now has the rest of the word below it
+        let mut below:u64 = 0;
+        for (node, ch) in path.iter().rev().zip(word.chars().rev()) {
+            below |= char_bit(ch);
+            node.as_ref().borrow_mut().below |= below;
         }
+
+        cur.as_ref().borrow_mut().frequency += frequency;
+        self.frequencies += frequency;
+        if let Some(reversed) = self.reversed.as_mut() { reversed.insert_with_frequency(&reverse(word), frequency); }
+        if let Some(infixes) = self.infixes.as_mut() {
+            for rotation in rotations(word) { infixes.insert_with_frequency(&rotation, frequency); }
+        }
+
+        if cur.as_ref().borrow().is_end_of_word { return; }
+        cur.as_ref().borrow_mut().is_end_of_word = true;
+        cur.as_ref().borrow_mut().order = self.insertions;
+        self.insertions += 1;
+        path.push(cur);
+
+        for node in path.iter() { node.as_ref().borrow_mut().words += 1; }
+        self.words += 1;
     }
 
     /// Remove word from prefix tree, if word doesn't exist stops as soon as possible
     /// 
     /// # Examples
     /// ```
+    /// # use prefix::tree::Trie;
     /// let mut trie:Trie = Trie::new();
     /// 
-    /// trie.inser("word");
+    /// trie.insert("word");
     /// 
     /// assert_eq!(trie.contains("word"), true);
     /// 
@@ -164,52 +325,223 @@ impl Trie {
     /// # Time Complexity
     /// Takes <i>O</i>(1) time
     pub fn remove(&mut self, word:&str) {
-        let mut cur:NodeRef = self.root.clone();
-        let mut cur_lvl:usize = 1;
-
-        // When going down in trie, store last node that is end of a word or has multiple children
-        let mut last:Option<(usize, NodeRef)> = None;
-        
-        let mut is_last_char:bool = false;
-        for (i, ch) in word.char_indices() {
-            if i == word.len() - 1 { is_last_char = true; }
+        let mut path:Vec<NodeRef> = Vec::with_capacity(word.len() + 1);
+        path.push(self.root.clone());
 
-            let ochild:Option<NodeRef> = cur.as_ref().borrow().get_child(ch).cloned();
-            if let Some(node) = ochild {
-                if is_last_char {
-                    if node.as_ref().borrow().children_size() > 0 {
-                        node.as_ref().borrow_mut().is_end_of_word = false;
-                    } else { Self::cut_child(last, word); }
-                    self.words -= 1;
-                    return;
-                }
-                if node.as_ref().borrow().is_end_of_word || node.as_ref().borrow().children_size() > 1 {
-                    last = Some( (cur_lvl, node.clone()) );
-                }
-                cur = node;
-                cur_lvl += 1;
-            } else { break; }
+        for ch in word.chars() {
+            let ochild:Option<NodeRef> = path[path.len() - 1].as_ref().borrow().get_child(ch).cloned();
+            let Some(node) = ochild else { return; };
+            path.push(node);
         }
 
-        Self::cut_child(last, word);
+        let last:&NodeRef = &path[path.len() - 1];
+        if word.is_empty() || !last.as_ref().borrow().is_end_of_word { return; }
+        last.as_ref().borrow_mut().is_end_of_word = false;
+        self.frequencies -= std::mem::take(&mut last.as_ref().borrow_mut().frequency);
+
+        for node in path.iter() { node.as_ref().borrow_mut().words -= 1; }
         self.words -= 1;
+
+        if let Some(reversed) = self.reversed.as_mut() { reversed.remove(&reverse(word)); }
+        if let Some(infixes) = self.infixes.as_mut() {
+            for rotation in rotations(word) { infixes.remove(&rotation); }
+        }
+
+        // Cut the highest node that no longer leads to any word, everything below it goes with it
+        let Some(i) = path.iter().skip(1).position(|node| node.as_ref().borrow().words == 0) else { return; };
+        let ch:char = path[i + 1].as_ref().borrow().val;
+        path[i].as_ref().borrow_mut().remove_child(ch);
+
+        // The chars of the cut branch may not be found below the nodes above it anymore
+        for node in path[..=i].iter().rev() { node.as_ref().borrow_mut().update_below(); }
     }
-    // Specific for remove function
-    fn cut_child(last:Option<(usize, NodeRef)>, word:&str) {
-        let Some( (lvl, node_to_change) ) = last else { return; };
-        
-        if let Some(child) = word.split_at(lvl + 1).0[lvl..lvl + 1].chars().next() {
-            println!("{}", child);
-            node_to_change.as_ref().borrow_mut().remove_child(child);
+
+    /// Start maintaining a suffix index: a second trie holding every word spelled backwards, kept up to date by
+    /// `insert` and `remove`. It makes suffix queries, see `SuffixSearchable`, as fast as prefix ones at the cost of
+    /// roughly doubling the memory used. Does nothing if the index is already enabled.
+    /// 
+    /// # Examples
+    /// ```
+    /// # use prefix::tree::Trie;
+    /// let mut trie:Trie = Trie::from(["nation"]);
+    /// trie.enable_suffix_index();
+    /// 
+    /// assert!(trie.has_suffix_index());
+    /// ```
+    /// # Time Complexity
+    /// Takes <i>O</i>(n) time to index the words already in the trie
+    pub fn enable_suffix_index(&mut self) {
+        if self.reversed.is_some() { return; }
+
+        let mut reversed:Trie = Trie::new();
+        for word in self.get_sorted_words() { reversed.insert_with_frequency(&reverse(&word), self.frequency(&word)); }
+
+        self.reversed = Some(Box::new(reversed));
+    }
+
+    /// Drop the suffix index and the memory it holds
+    pub fn disable_suffix_index(&mut self) {
+        self.reversed = None;
+    }
+
+    pub fn has_suffix_index(&self) -> bool {
+        self.reversed.is_some()
+    }
+
+    /// Start maintaining an infix index: a second trie holding every entry made of several words once for each of its
+    /// inner words, starting at that word, kept up to date by `insert` and `remove`. It lets completion match any word
+    /// of an entry, see `InfixCompletable`. Entries containing the control char `'\u{1f}'` are left out of it, they
+    /// are only found by their first word. Does nothing if the index is already enabled.
+    /// 
+    /// # Examples
+    /// ```
+    /// # use prefix::tree::Trie;
+    /// let mut trie:Trie = Trie::from(["new york", "york"]);
+    /// trie.enable_infix_index();
+    /// 
+    /// assert!(trie.has_infix_index());
+    /// ```
+    /// # Time Complexity
+    /// Takes <i>O</i>(n * words per entry) time to index the entries already in the trie
+    pub fn enable_infix_index(&mut self) {
+        if self.infixes.is_some() { return; }
+
+        let mut infixes:Trie = Trie::new();
+        for word in self.get_sorted_words() {
+            let frequency:usize = self.frequency(&word);
+            for rotation in rotations(&word) { infixes.insert_with_frequency(&rotation, frequency); }
         }
+
+        self.infixes = Some(Box::new(infixes));
+    }
+
+    /// Drop the infix index and the memory it holds
+    pub fn disable_infix_index(&mut self) {
+        self.infixes = None;
+    }
+
+    pub fn has_infix_index(&self) -> bool {
+        self.infixes.is_some()
+    }
+
+    /// Number of times `word` has been inserted, 0 if it is not in the trie
+    /// 
+    /// # Examples
+    /// ```
+    /// # use prefix::tree::Trie;
+    /// let trie:Trie = Trie::from(["to", "be", "or", "not", "to", "be"]);
+    /// 
+    /// assert_eq!(trie.frequency("be"), 2);
+    /// assert_eq!(trie.frequency("not"), 1);
+    /// assert_eq!(trie.frequency("question"), 0);
+    /// ```
+    pub fn frequency(&self, word:&str) -> usize {
+        let Some(node) = self.go_to(word) else { return 0; };
+        let node = node.as_ref().borrow();
+
+        if node.is_end_of_word { node.frequency } else { 0 }
+    }
+
+    /// Sum of the frequencies of all the words in the trie
+    pub fn total_frequency(&self) -> usize {
+        self.frequencies
+    }
+
+    /// Check if both tries hold the same words with the same frequencies, where `==` only compares the words
+    /// 
+    /// # Examples
+    /// ```
+    /// # use prefix::tree::Trie;
+    /// let trie:Trie = Trie::from(["to", "be", "to"]);
+    /// 
+    /// assert!(trie.eq_with_frequencies(&Trie::from(["be", "to", "to"])));
+    /// assert_eq!(trie, Trie::from(["to", "be"]));
+    /// assert!(!trie.eq_with_frequencies(&Trie::from(["to", "be"])));
+    /// ```
+    /// # Time Complexity
+    /// Takes <i>O</i>(n) time
+    pub fn eq_with_frequencies(&self, other:&Trie) -> bool {
+        if self.words != other.words || self.frequencies != other.frequencies { return false; }
+
+        let entries = |trie:&Trie| {
+            let mut res:Vec<(String, usize)> = Vec::with_capacity(trie.words);
+            collect_with_frequency(&trie.root, &mut String::with_capacity(8), 0, None, &mut res);
+            res
+        };
+        entries(self) == entries(other)
+    }
+
+    /// Number of words that start with `prefix`, the prefix itself included if it is a word
+    /// 
+    /// # Examples
+    /// ```
+    /// # use prefix::tree::Trie;
+    /// let trie:Trie = Trie::from(["an", "and", "ant", "bee"]);
+    /// 
+    /// assert_eq!(trie.count_prefix("an"), 3);
+    /// assert_eq!(trie.count_prefix(""), 4);
+    /// assert_eq!(trie.count_prefix("x"), 0);
+    /// ```
+    /// # Time Complexity
+    /// Takes <i>O</i>(prefix) time
+    pub fn count_prefix(&self, prefix:&str) -> usize {
+        self.go_to(prefix).map_or(0, |node| node.as_ref().borrow().words)
+    }
+
+    /// For every char that can follow `prefix`, number of words that start with `prefix` and that char. Pairs are
+    /// ordered by char
+    /// 
+    /// # Examples
+    /// ```
+    /// # use prefix::tree::Trie;
+    /// let trie:Trie = Trie::from(["an", "and", "ant", "antler", "as"]);
+    /// 
+    /// assert_eq!(trie.child_counts("a"), vec![('n', 4), ('s', 1)]);
+    /// assert_eq!(trie.child_counts("an"), vec![('d', 1), ('t', 2)]);
+    /// ```
+    pub fn child_counts(&self, prefix:&str) -> Vec<(char, usize)> {
+        let Some(node) = self.go_to(prefix) else { return Vec::new(); };
+
+        node.as_ref().borrow().sorted_children().into_iter()
+            .map(|(ch, child)| (ch, child.as_ref().borrow().words))
+            .collect()
+    }
+
+    /// Get a `TrieCursor` at the root, to follow a prefix one typed char at a time
+    /// 
+    /// # Examples
+    /// ```
+    /// # use prefix::tree::Trie;
+    /// let trie:Trie = Trie::from(["go", "gone"]);
+    /// let mut cursor = trie.cursor();
+    /// 
+    /// "go".chars().for_each(|ch| { cursor.push(ch); });
+    /// assert!(cursor.is_word());
+    /// ```
+    pub fn cursor(&self) -> TrieCursor<'_> {
+        TrieCursor::new(self)
+    }
+
+    /// Build a `Matcher`, an Aho-Corasick automaton finding all the words of the trie in a text at once. The trie is
+    /// left untouched, later changes to it don't reach the matcher
+    /// 
+    /// # Examples
+    /// ```
+    /// # use prefix::tree::Trie;
+    /// let trie:Trie = Trie::from(["cat", "dog"]);
+    /// 
+    /// let found:Vec<_> = trie.to_matcher().find_iter("hotdog, concatenate").collect();
+    /// assert_eq!(found, vec![(3, 6, "dog"), (11, 14, "cat")]);
+    /// ```
+    pub fn to_matcher(&self) -> Matcher {
+        Matcher::from(self)
     }
 
     /// Try find a node that coincides with end of the word
     pub fn go_to(&self, word:&str) -> Option<NodeRef> {
         let mut cur:NodeRef = self.root.clone();
 
-        let mut pchars = word.chars();
-        while let Some(ch) = pchars.next() {
+        for ch in word.chars() {
             let ochild = cur.as_ref().borrow().get_child(ch).cloned();
             
             if let Some(node) = ochild {
@@ -218,6 +550,81 @@ impl Trie {
         }
         Some(cur)
     }
+
+    /// Get the longest word of the trie that is a prefix of `input`, returned as a slice of `input`
+    /// 
+    /// # Examples
+    /// ```
+    /// # use prefix::tree::Trie;
+    /// let trie:Trie = Trie::from(["/api", "/api/users", "/static"]);
+    /// 
+    /// assert_eq!(trie.longest_prefix_of("/api/users/42"), Some("/api/users"));
+    /// assert_eq!(trie.longest_prefix_of("/api/user"), Some("/api"));
+    /// assert_eq!(trie.longest_prefix_of("/home"), None);
+    /// ```
+    /// # Time Complexity
+    /// Takes <i>O</i>(input) time
+    pub fn longest_prefix_of<'a>(&self, input:&'a str) -> Option<&'a str> {
+        self.prefixes_of(input).pop()
+    }
+
+    /// Get every word of the trie that is a prefix of `input`, shortest first, returned as slices of `input`
+    /// 
+    /// # Examples
+    /// ```
+    /// # use prefix::tree::Trie;
+    /// let trie:Trie = Trie::from(["a", "an", "and", "ant"]);
+    /// 
+    /// assert_eq!(trie.prefixes_of("andante"), vec!["a", "an", "and"]);
+    /// ```
+    /// # Time Complexity
+    /// Takes <i>O</i>(input) time
+    pub fn prefixes_of<'a>(&self, input:&'a str) -> Vec<&'a str> {
+        let mut res:Vec<&str> = Vec::with_capacity(4);
+        let mut cur:NodeRef = self.root.clone();
+
+        for (i, ch) in input.char_indices() {
+            let ochild:Option<NodeRef> = cur.as_ref().borrow().get_child(ch).cloned();
+            let Some(node) = ochild else { break; };
+
+            if node.as_ref().borrow().is_end_of_word { res.push(&input[..i + ch.len_utf8()]); }
+            cur = node;
+        }
+        res
+    }
+}
+
+pub(crate) fn reverse(word:&str) -> String {
+    word.chars().rev().collect()
+}
+
+// Separates the rotated part of an entry from the words that came before it
+const ROTATION_MARK:char = '\u{1f}';
+
+/// Byte offsets of the words of an entry after the first one, a word starting after whitespace. None for an entry
+/// containing `ROTATION_MARK`, its rotations couldn't be turned back into it
+pub(crate) fn inner_word_starts(word:&str) -> impl Iterator<Item = usize> + '_ {
+    let rotatable:bool = !word.contains(ROTATION_MARK);
+
+    word.char_indices()
+        .filter(move |_| rotatable)
+        .zip(word.chars().skip(1))
+        .filter(|((_, ch), next)| ch.is_whitespace() && !next.is_whitespace())
+        .map(|((i, ch), _)| i + ch.len_utf8())
+}
+
+/// The entry rotated to start at each of its inner words, `"new york city"` gives `"york city\u{1f}new "` and
+/// `"city\u{1f}new york "`
+pub(crate) fn rotations(word:&str) -> Vec<String> {
+    inner_word_starts(word).map(|i| format!("{}{ROTATION_MARK}{}", &word[i..], &word[..i])).collect()
+}
+
+/// Turn a rotation back into its entry
+pub(crate) fn unrotate(rotation:&str) -> String {
+    match rotation.rsplit_once(ROTATION_MARK) {
+        Some((tail, head)) => format!("{head}{tail}"),
+        None => rotation.to_string(),
+    }
 }
 
 pub struct TrieBuilder {
@@ -230,7 +637,11 @@ impl TrieBuilder {
     /// lines will be inserted in the tree
     /// # Examples 
     /// ```
+    /// # use std::{fs::File, io::BufReader};
+    /// # use prefix::tree::{Trie, TrieBuilder};
+    /// # let reader = BufReader::new(File::open("./data/10k_cmn_words.txt").unwrap());
     /// let trie:Trie = TrieBuilder::from(reader).lines(100).build();
+    /// ```
     pub fn lines(mut self, n:usize) -> Self {
         self.lines = Some(n);
         self
@@ -238,8 +649,49 @@ impl TrieBuilder {
 
     pub fn build(self) -> Trie {
         let mut trie:Trie = Trie::new();
+        self.for_each_line(|line| trie.insert(line));
+
+        trie
+    }
+
+    /// Build a `SuffixTrie` from the same lines, to search words by any part of them
+    /// # Examples 
+    /// ```
+    /// # use std::{fs::File, io::BufReader};
+    /// # use prefix::{substring::SuffixTrie, tree::TrieBuilder};
+    /// # let reader = BufReader::new(File::open("./data/10k_cmn_words.txt").unwrap());
+    /// let suffixes:SuffixTrie = TrieBuilder::from(reader).lines(1000).build_suffix_trie();
+    /// 
+    /// assert!(suffixes.containing("ough").contains(&"through"));
+    /// ```
+    pub fn build_suffix_trie(self) -> SuffixTrie {
+        let mut words:Vec<String> = Vec::new();
+        self.for_each_line(|line| words.push(line.to_string()));
+
+        SuffixTrie::from_words(words)
+    }
 
-        let Some(mut reader) = self.reader else { return trie; };
+    /// Build an `NgramTrie` counting sequences of up to `order` words, each line being a sentence of words separated by
+    /// whitespace
+    /// # Examples 
+    /// ```
+    /// # use std::{fs::File, io::BufReader};
+    /// # use prefix::{ngram::NgramTrie, tree::TrieBuilder};
+    /// # let reader = BufReader::new(File::open("./data/10k_cmn_words.txt").unwrap());
+    /// let ngrams:NgramTrie = TrieBuilder::from(reader).lines(1000).build_ngrams(3);
+    /// 
+    /// assert_eq!(ngrams.count(&["the"]), 1);
+    /// ```
+    pub fn build_ngrams(self, order:usize) -> NgramTrie {
+        let mut ngrams:NgramTrie = NgramTrie::new(order);
+        self.for_each_line(|line| ngrams.add_sentence(&line.split_whitespace().collect::<Vec<&str>>()));
+
+        ngrams
+    }
+
+    // Call `f` with every trimmed line of the reader, up to the line limit, then rewind the reader
+    pub(crate) fn for_each_line<F: FnMut(&str)>(self, mut f:F) {
+        let Some(mut reader) = self.reader else { return; };
 
         let lines = if let Some(l) = self.lines { l } else { usize::MAX };
 
@@ -251,11 +703,9 @@ impl TrieBuilder {
                 continue;
             };
 
-            trie.insert(line.trim());
+            f(line.trim());
         }
         let _ = reader.seek(SeekFrom::Start(0));
-
-        trie
     }
 }
 
@@ -263,7 +713,9 @@ impl From<BufReader<File>> for TrieBuilder {
     /// Iterate through a file and insert all the words into prefix tree
     /// 
     /// # Examples 
-    /// ```
+    /// ```no_run
+    /// # use std::{fs::File, io::BufReader};
+    /// # use prefix::tree::{Trie, TrieBuilder};
     /// let file = File::open("./file/path.txt")
     ///     .expect("File not found");
     ///